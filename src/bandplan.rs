@@ -121,3 +121,16 @@ pub fn get_band(bandplan: &Bandplan, frequency: Decimal) -> Option<&Band> {
     }
     None
 }
+
+// collect the nominal repeater offsets of all amateur bands, sorted and deduplicated
+pub fn get_repeater_offsets(bandplan: &Bandplan) -> Vec<Decimal> {
+    let mut offsets: Vec<Decimal> = bandplan.bands.iter()
+        .filter(|band| band.is_amateur)
+        .filter_map(|band| band.nominal_offsets.as_ref())
+        .flatten()
+        .cloned()
+        .collect();
+    offsets.sort();
+    offsets.dedup();
+    offsets
+}
@@ -0,0 +1,142 @@
+// src/cards.rs
+
+use std::collections::HashMap;
+use rust_decimal::prelude::ToPrimitive;
+use clap::ValueEnum;
+use crate::*;
+use crate::structures::*;
+
+// Printable pocket reference cards:
+// One card per zone, listing the zone's channels in the order they appear on the radio.
+// Output is a self-contained HTML page with one card per printed page, sized with CSS @page.
+// Channel and talkgroup names are truncated to the target radio's display width, if known.
+
+#[derive(Debug, Clone, ValueEnum, PartialEq, Default)]
+#[clap(rename_all = "kebab_case")]
+pub enum PageSize {
+    #[default]
+    Letter,
+    A4,
+    A5,
+    A6,
+    IndexCard, // 5x3in
+}
+
+impl PageSize {
+    fn css(&self) -> &'static str {
+        match self {
+            PageSize::Letter => "letter",
+            PageSize::A4 => "A4",
+            PageSize::A5 => "A5",
+            PageSize::A6 => "A6",
+            PageSize::IndexCard => "5in 3in",
+        }
+    }
+}
+
+pub struct CardOptions {
+    pub page_size: PageSize,
+    pub font_size: f64, // points
+    pub name_width: Option<usize>, // truncate names to this many characters
+}
+
+fn truncate(name: &str, width: Option<usize>) -> String {
+    match width {
+        Some(width) => name.chars().take(width).collect(),
+        None => name.to_string(),
+    }
+}
+
+fn mhz(hz: f64) -> String {
+    format!("{:.4}", hz / 1e6)
+}
+
+fn tone_str(tone: &Option<Tone>) -> String {
+    match tone {
+        Some(Tone::Ctcss(freq)) => format!("{:.1}", freq),
+        Some(Tone::Dcs(code)) => code.clone(),
+        None => "".to_string(),
+    }
+}
+
+fn card_row(position: usize, channel: &Channel, talkgroups: &HashMap<&String, &String>, width: Option<usize>) -> Vec<String> {
+    let rx = channel.frequency_rx.to_f64().unwrap();
+    let tx = channel.frequency_tx.to_f64().unwrap();
+    let offset = if channel.rx_only {
+        "RX".to_string()
+    } else if tx == rx {
+        "".to_string()
+    } else {
+        format!("{:+.3}", (tx - rx) / 1e6)
+    };
+    let mut tone = String::new();
+    let mut timeslot = String::new();
+    let mut talkgroup = String::new();
+    if let Some(fm) = &channel.fm {
+        // the transmit tone is the one needed to access a repeater
+        tone = tone_str(&fm.tone_tx);
+    }
+    if let Some(dmr) = &channel.dmr {
+        tone = format!("CC{}", dmr.color_code);
+        timeslot = dmr.timeslot.to_string();
+        if let Some(uid) = &dmr.talkgroup {
+            talkgroup = truncate(talkgroups.get(uid).map_or(uid, |n| *n), width);
+        }
+    }
+    vec![
+        position.to_string(),
+        truncate(&channel.name, width),
+        mhz(rx),
+        offset,
+        tone,
+        timeslot,
+        talkgroup,
+    ]
+}
+
+/// Render pocket reference cards for each zone as a self-contained HTML page
+pub fn cards(opt: &Opt, codeplug: &Codeplug, options: &CardOptions) -> Result<String, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let channels: HashMap<&String, &Channel> = codeplug.channels.iter().map(|c| (&c.uid, c)).collect();
+    let talkgroups: HashMap<&String, &String> = codeplug.talkgroups.iter().map(|t| (&t.uid, &t.name)).collect();
+    let header = ["#", "Name", "Freq", "Offset", "Tone", "TS", "Talkgroup"];
+
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str(&format!("<title>Codeplug Cards ({})</title>\n", report::escape_html(&codeplug.source)));
+    output.push_str("<style>\n");
+    output.push_str(&format!("@page {{ size: {}; margin: 0.25in; }}\n", options.page_size.css()));
+    output.push_str(&format!("body {{ font-family: sans-serif; font-size: {}pt; margin: 0; }}\n", options.font_size));
+    output.push_str(".card { break-after: page; page-break-after: always; }\n");
+    output.push_str(".card:last-child { break-after: auto; page-break-after: auto; }\n");
+    output.push_str("h1 { font-size: 1.2em; margin: 0 0 0.3em 0; }\n");
+    output.push_str("table { border-collapse: collapse; width: 100%; }\n");
+    output.push_str("th, td { border: 1px solid #000; padding: 0.05em 0.3em; text-align: left; white-space: nowrap; }\n");
+    output.push_str("td.num { text-align: right; font-family: monospace; }\n");
+    output.push_str("</style>\n</head>\n<body>\n");
+    for zone in &codeplug.zones {
+        output.push_str("<div class=\"card\">\n");
+        output.push_str(&format!("<h1>{}</h1>\n", report::escape_html(&zone.name)));
+        output.push_str("<table>\n<tr>");
+        for column in header {
+            output.push_str(&format!("<th>{}</th>", column));
+        }
+        output.push_str("</tr>\n");
+        for (ii, uid) in zone.channels.iter().enumerate() {
+            let Some(channel) = channels.get(uid) else {
+                uprintln!(opt, Stderr, Color::Yellow, None, "Zone {} references unknown channel, skipping: {}", zone.name, uid);
+                continue;
+            };
+            output.push_str("<tr>");
+            for (column, cell) in card_row(ii + 1, channel, &talkgroups, options.name_width).iter().enumerate() {
+                // frequency and offset columns line up better in a fixed-width font
+                let class = if matches!(column, 0 | 2 | 3) { " class=\"num\"" } else { "" };
+                output.push_str(&format!("<td{}>{}</td>", class, report::escape_html(cell)));
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n</div>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    Ok(output)
+}
@@ -0,0 +1,365 @@
+// src/csvdir.rs
+
+use std::path::Path;
+use serde_json::{Map, Value};
+use crate::*;
+
+// CSV directory intermediary format:
+// A codeplug is a directory of plain CSV files, with one row per entity:
+// - channels.csv, zones.csv, scanlists.csv, talkgroups.csv, talkgroup_lists.csv
+// - contacts.csv, broadcast_memories.csv, airband_memories.csv
+// - config.csv, with "key" and "value" columns for schema_version, source, and config.*
+// Columns are the intermediary field names, nested fields use dotted paths, e.g. "dmr.timeslot",
+// "fm.tone_rx.Ctcss" or "extensions.anytone_x78.Busy Lock". Enum variants that carry data get a
+// column per variant, e.g. "power" holds "Default" and "power.Watts" holds a wattage.
+// Lists of references (zone and scan list channels, talkgroup list talkgroups) are comma-separated uids.
+// Lists of records in config.csv use an index in the key, e.g. "config.dmr_configuration.id_list.0.id".
+// An empty cell is an unset field, an empty string is written as "".
+// Cells are typed using the JSON Schema of the intermediary structures.
+
+const TABLES: [&str; 8] = [
+    "channels",
+    "zones",
+    "scanlists",
+    "talkgroups",
+    "talkgroup_lists",
+    "contacts",
+    "broadcast_memories",
+    "airband_memories",
+];
+
+const EMPTY_STRING: &str = "\"\"";
+
+// Scalar cell types
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    Array(Box<Kind>), // comma-separated scalars
+}
+
+// Step into a nested value
+#[derive(Debug)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+// Follow a schema $ref into the root definitions
+fn deref<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => {
+            let name = reference.trim_start_matches("#/definitions/");
+            root.get("definitions").and_then(|d| d.get(name)).unwrap_or(schema)
+        }
+        None => schema,
+    }
+}
+
+// Cell type of a schema, None if it is not a scalar (or list of scalars)
+fn kind(root: &Value, schema: &Value) -> Option<Kind> {
+    let schema = deref(root, schema);
+    let type_name = match schema.get("type") {
+        Some(Value::String(t)) => t.as_str(),
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).find(|t| *t != "null")?,
+        _ => return None,
+    };
+    match type_name {
+        "string" => Some(Kind::String),
+        "integer" => Some(Kind::Integer),
+        "number" => Some(Kind::Number),
+        "boolean" => Some(Kind::Boolean),
+        "array" => Some(Kind::Array(Box::new(kind(root, schema.get("items")?)?))),
+        _ => None,
+    }
+}
+
+fn prepend(step: Step, resolved: Option<(Vec<Step>, Kind)>) -> Option<(Vec<Step>, Kind)> {
+    resolved.map(|(mut steps, kind)| {
+        steps.insert(0, step);
+        (steps, kind)
+    })
+}
+
+// Resolve a dotted column path against the schema, returning the steps into the value and the cell type
+fn resolve(root: &Value, schema: &Value, path: &[&str]) -> Option<(Vec<Step>, Kind)> {
+    let schema = deref(root, schema);
+    // optional fields and enums are unions, use the first branch that accepts the path
+    for union in ["allOf", "anyOf", "oneOf"] {
+        if let Some(branches) = schema.get(union).and_then(Value::as_array) {
+            for branch in branches {
+                if let Some(resolved) = resolve(root, branch, path) {
+                    return Some(resolved);
+                }
+            }
+        }
+    }
+    let Some((head, rest)) = path.split_first() else {
+        return kind(root, schema).map(|k| (Vec::new(), k));
+    };
+    if let Some(field) = schema.get("properties").and_then(|p| p.get(*head)) {
+        return prepend(Step::Key(head.to_string()), resolve(root, field, rest));
+    }
+    if let (Some(items), Ok(index)) = (schema.get("items"), head.parse::<usize>()) {
+        return prepend(Step::Index(index), resolve(root, items, rest));
+    }
+    if let Some(values) = schema.get("additionalProperties").filter(|v| v.is_object()) {
+        if let Some(resolved) = prepend(Step::Key(head.to_string()), resolve(root, values, rest)) {
+            return Some(resolved);
+        }
+        // map keys may contain dots, so a map of scalars takes the rest of the path as the key
+        return kind(root, values).map(|k| (vec![Step::Key(path.join("."))], k));
+    }
+    None
+}
+
+// Convert a cell to a value, None if the cell is empty (unset)
+fn parse_cell(kind: &Kind, cell: &str) -> Result<Option<Value>, Box<dyn Error>> {
+    if cell.is_empty() {
+        return Ok(match kind {
+            Kind::Array(_) => Some(Value::Array(Vec::new())),
+            _ => None,
+        });
+    }
+    let value = match kind {
+        Kind::String if cell == EMPTY_STRING => Value::String(String::new()),
+        Kind::String => Value::String(cell.to_string()),
+        Kind::Integer => match cell.parse::<i64>() {
+            Ok(v) => Value::from(v),
+            Err(_) => Value::from(cell.parse::<u64>().map_err(|_| format!("Invalid integer: {}", cell))?),
+        },
+        Kind::Number => {
+            let v = cell.parse::<f64>().map_err(|_| format!("Invalid number: {}", cell))?;
+            Value::from(v)
+        }
+        Kind::Boolean => match cell.to_lowercase().as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => return Err(format!("Invalid boolean: {}", cell).into()),
+        },
+        Kind::Array(item) => {
+            let mut items = Vec::new();
+            for part in cell.split(',') {
+                if let Some(v) = parse_cell(item, part.trim())? {
+                    items.push(v);
+                }
+            }
+            Value::Array(items)
+        }
+    };
+    Ok(Some(value))
+}
+
+/// Set a cell in a record, creating nested objects and arrays along the way
+pub fn insert(root: &Value, schema: &Value, record: &mut Value, column: &str, cell: &str) -> Result<(), Box<dyn Error>> {
+    let path: Vec<&str> = column.split('.').collect();
+    let Some((steps, kind)) = resolve(root, schema, &path) else {
+        return Err(format!("Unknown column: {}", column).into());
+    };
+    let Some(value) = parse_cell(&kind, cell).map_err(|e| format!("Column {}: {}", column, e))? else {
+        return Ok(());
+    };
+    let mut target = record;
+    for step in steps {
+        target = match step {
+            Step::Key(key) => {
+                if !target.is_object() {
+                    *target = Value::Object(Map::new());
+                }
+                target.as_object_mut().unwrap().entry(key).or_insert(Value::Null)
+            }
+            Step::Index(index) => {
+                if !target.is_array() {
+                    *target = Value::Array(Vec::new());
+                }
+                let items = target.as_array_mut().unwrap();
+                if items.len() <= index {
+                    items.resize(index + 1, Value::Null);
+                }
+                &mut items[index]
+            }
+        };
+    }
+    *target = value;
+    Ok(())
+}
+
+/// Unset a cell in a record
+pub fn remove(root: &Value, schema: &Value, record: &mut Value, column: &str) -> Result<(), Box<dyn Error>> {
+    let path: Vec<&str> = column.split('.').collect();
+    let Some((steps, _)) = resolve(root, schema, &path) else {
+        return Err(format!("Unknown column: {}", column).into());
+    };
+    let mut target = record;
+    for step in steps {
+        let next = match step {
+            Step::Key(key) => target.get_mut(&key),
+            Step::Index(index) => target.get_mut(index),
+        };
+        match next {
+            Some(next) => target = next,
+            None => return Ok(()), // already unset
+        }
+    }
+    *target = Value::Null;
+    Ok(())
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::String(s) if s.is_empty() => EMPTY_STRING.to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Flatten a value into (column, cell) pairs, unset fields are left out
+pub fn flatten(prefix: &str, value: &Value, cells: &mut Vec<(String, String)>) {
+    let join = |key: &str| if prefix.is_empty() { key.to_string() } else { format!("{}.{}", prefix, key) };
+    match value {
+        Value::Null => {}
+        Value::Object(map) => {
+            for (key, v) in map {
+                flatten(&join(key), v, cells);
+            }
+        }
+        Value::Array(items) if items.iter().all(|v| !v.is_object() && !v.is_array()) => {
+            cells.push((prefix.to_string(), items.iter().map(cell).collect::<Vec<String>>().join(",")));
+        }
+        Value::Array(items) => {
+            for (ii, v) in items.iter().enumerate() {
+                flatten(&join(&ii.to_string()), v, cells);
+            }
+        }
+        other => cells.push((prefix.to_string(), cell(other))),
+    }
+}
+
+fn write_table(path: &Path, records: &[Value]) -> Result<(), Box<dyn Error>> {
+    let rows: Vec<Vec<(String, String)>> = records.iter().map(|record| {
+        let mut cells = Vec::new();
+        flatten("", record, &mut cells);
+        cells
+    }).collect();
+    // columns are the union of all rows, in the order they first appear
+    let mut header: Vec<String> = Vec::new();
+    for row in &rows {
+        for (column, _) in row {
+            if !header.contains(column) {
+                header.push(column.clone());
+            }
+        }
+    }
+    let mut writer = csv::Writer::from_path(path)?;
+    if !header.is_empty() {
+        writer.write_record(&header)?;
+    }
+    for row in &rows {
+        let record: Vec<&str> = header.iter()
+            .map(|column| row.iter().find(|(c, _)| c == column).map_or("", |(_, v)| v.as_str()))
+            .collect();
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a codeplug as a directory of CSV files
+pub fn write(opt: &Opt, path: &Path, codeplug: &structures::Codeplug) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    std::fs::create_dir_all(path)?;
+    let mut value = serde_json::to_value(codeplug)?;
+    let root = value.as_object_mut().unwrap();
+    // talkgroup lists hold copies of their talkgroups, write them as references
+    if let Some(Value::Array(talkgroup_lists)) = root.get_mut("talkgroup_lists") {
+        for talkgroup_list in talkgroup_lists {
+            if let Some(Value::Array(talkgroups)) = talkgroup_list.get_mut("talkgroups") {
+                *talkgroups = talkgroups.iter().map(|tg| tg["uid"].clone()).collect();
+            }
+        }
+    }
+    for table in TABLES {
+        let records = match root.remove(table) {
+            Some(Value::Array(records)) => records,
+            _ => Vec::new(),
+        };
+        write_table(&path.join(format!("{}.csv", table)), &records)?;
+    }
+    // everything else is codeplug-wide, written as key/value pairs
+    let mut cells = Vec::new();
+    flatten("", &value, &mut cells);
+    let mut writer = csv::Writer::from_path(path.join("config.csv"))?;
+    writer.write_record(["key", "value"])?;
+    for (key, cell) in &cells {
+        writer.write_record([key, cell])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_table(root: &Value, schema: &Value, path: &Path) -> Result<Vec<Value>, Box<dyn Error>> {
+    let mut records = Vec::new();
+    if !path.exists() {
+        return Ok(records);
+    }
+    let mut reader = csv::Reader::from_path(path)?;
+    let header = reader.headers()?.clone();
+    for (row, result) in reader.records().enumerate() {
+        let csv_record = result?;
+        let mut record = Value::Object(Map::new());
+        for (column, cell) in header.iter().zip(csv_record.iter()) {
+            insert(root, schema, &mut record, column, cell)
+                .map_err(|e| format!("{} row {}: {}", path.display(), row + 1, e))?;
+        }
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Read a directory of CSV files into an untyped codeplug
+pub fn read(opt: &Opt, path: &Path) -> Result<Value, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let schema = serde_json::to_value(schemars::schema_for!(structures::Codeplug))?;
+    let mut value = Value::Object(Map::new());
+    // codeplug-wide key/value pairs
+    let config_path = path.join("config.csv");
+    if config_path.exists() {
+        let mut reader = csv::Reader::from_path(&config_path)?;
+        for (row, result) in reader.records().enumerate() {
+            let csv_record = result?;
+            let (Some(key), Some(cell)) = (csv_record.get(0), csv_record.get(1)) else {
+                return Err(format!("{} row {}: expected key and value", config_path.display(), row + 1).into());
+            };
+            insert(&schema, &schema, &mut value, key, cell)
+                .map_err(|e| format!("{} row {}: {}", config_path.display(), row + 1, e))?;
+        }
+    }
+    let mut tables = Map::new();
+    for table in TABLES {
+        let mut items = deref(&schema, &schema["properties"][table]["items"]).clone();
+        if table == "talkgroup_lists" {
+            // talkgroups are read as references, see below
+            items["properties"]["talkgroups"] = serde_json::json!({"type": "array", "items": {"type": "string"}});
+        }
+        let records = read_table(&schema, &items, &path.join(format!("{}.csv", table)))?;
+        tables.insert(table.to_string(), Value::Array(records));
+    }
+    // talkgroup lists reference their talkgroups by uid, replace the references with copies
+    let talkgroups = tables["talkgroups"].as_array().unwrap().clone();
+    for talkgroup_list in tables["talkgroup_lists"].as_array_mut().unwrap() {
+        if let Some(Value::Array(members)) = talkgroup_list.get_mut("talkgroups") {
+            let mut copies = Vec::new();
+            for uid in members.iter() {
+                match talkgroups.iter().find(|tg| tg["uid"] == *uid) {
+                    Some(talkgroup) => copies.push(talkgroup.clone()),
+                    None => return Err(format!("Talkgroup list references unknown talkgroup: {}", uid).into()),
+                }
+            }
+            *members = copies;
+        }
+    }
+    value.as_object_mut().unwrap().extend(tables);
+    Ok(value)
+}
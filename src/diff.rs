@@ -0,0 +1,305 @@
+// src/diff.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::*;
+use crate::structures::*;
+
+// Semantic diff:
+// Entities are matched by identity rather than index, so reindexing does not show up as a change.
+// Matching is by name first, then by a secondary identity (channels: frequencies and mode,
+// talkgroups: ID and call type) so that renamed entities are reported as modified.
+// Fields are compared after flattening (see csvdir.rs), with references shown as names.
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Channel,
+    Zone,
+    ScanList,
+    Talkgroup,
+    TalkgroupList,
+}
+
+impl std::fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EntityKind::Channel => write!(f, "channel"),
+            EntityKind::Zone => write!(f, "zone"),
+            EntityKind::ScanList => write!(f, "scan list"),
+            EntityKind::Talkgroup => write!(f, "talkgroup"),
+            EntityKind::TalkgroupList => write!(f, "talkgroup list"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>, // None if unset
+    pub new: Option<String>, // None if unset
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Change {
+    Added { kind: EntityKind, name: String, entity: Value },
+    Removed { kind: EntityKind, name: String, entity: Value },
+    Modified { kind: EntityKind, name: String, fields: Vec<FieldChange> },
+}
+
+// An entity prepared for comparison
+struct Entry {
+    name: String,
+    alt: Option<String>, // secondary identity
+    value: Value, // without index and uid
+    fields: Vec<(String, String)>,
+}
+
+/// Prepare an entity value for comparison: index and uid are dropped, and talkgroup lists
+/// (which hold copies of their talkgroups) list talkgroups by name
+pub fn normalize(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.remove("index");
+        object.remove("uid");
+        if let Some(Value::Array(talkgroups)) = object.get_mut("talkgroups") {
+            *talkgroups = talkgroups.iter().map(|tg| if tg.is_object() { tg["name"].clone() } else { tg.clone() }).collect();
+        }
+    }
+    value
+}
+
+fn entry<T: Serialize>(entity: &T, name: &str, alt: Option<String>) -> Result<Entry, Box<dyn Error>> {
+    let value = normalize(serde_json::to_value(entity)?);
+    let mut fields = Vec::new();
+    csvdir::flatten("", &value, &mut fields);
+    Ok(Entry { name: name.to_string(), alt, value, fields })
+}
+
+fn entries(kind: EntityKind, codeplug: &Codeplug) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let mut entries = Vec::new();
+    match kind {
+        EntityKind::Channel => for channel in &codeplug.channels {
+            let alt = format!("{} {} {:?}", channel.frequency_rx, channel.frequency_tx, channel.mode);
+            entries.push(entry(channel, &channel.name, Some(alt))?);
+        },
+        EntityKind::Zone => for zone in &codeplug.zones {
+            entries.push(entry(zone, &zone.name, None)?);
+        },
+        EntityKind::ScanList => for scanlist in &codeplug.scanlists {
+            entries.push(entry(scanlist, &scanlist.name, None)?);
+        },
+        EntityKind::Talkgroup => for talkgroup in &codeplug.talkgroups {
+            let alt = format!("{} {:?}", talkgroup.id, talkgroup.call_type);
+            entries.push(entry(talkgroup, &talkgroup.name, Some(alt))?);
+        },
+        EntityKind::TalkgroupList => for talkgroup_list in &codeplug.talkgroup_lists {
+            entries.push(entry(talkgroup_list, &talkgroup_list.name, None)?);
+        },
+    }
+    Ok(entries)
+}
+
+fn diff_fields(old: &Entry, new: &Entry) -> Vec<FieldChange> {
+    let get = |entry: &Entry, field: &str| entry.fields.iter().find(|(f, _)| f == field).map(|(_, v)| v.clone());
+    let mut names: Vec<&String> = old.fields.iter().map(|(f, _)| f).collect();
+    for (field, _) in &new.fields {
+        if !names.contains(&field) {
+            names.push(field);
+        }
+    }
+    names.into_iter().filter_map(|field| {
+        let (old_value, new_value) = (get(old, field), get(new, field));
+        (old_value != new_value).then(|| FieldChange { field: field.clone(), old: old_value, new: new_value })
+    }).collect()
+}
+
+fn diff_kind(kind: EntityKind, old: Vec<Entry>, new: Vec<Entry>) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let mut new_matched = vec![false; new.len()];
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut unmatched: Vec<usize> = Vec::new();
+    // match by name
+    for (oi, o) in old.iter().enumerate() {
+        match (0..new.len()).find(|&ni| !new_matched[ni] && new[ni].name == o.name) {
+            Some(ni) => {
+                new_matched[ni] = true;
+                pairs.push((oi, ni));
+            }
+            None => unmatched.push(oi),
+        }
+    }
+    // then by secondary identity, to catch renames
+    for oi in unmatched {
+        let found = old[oi].alt.as_ref().and_then(|alt| {
+            (0..new.len()).find(|&ni| !new_matched[ni] && new[ni].alt.as_ref() == Some(alt))
+        });
+        match found {
+            Some(ni) => {
+                new_matched[ni] = true;
+                pairs.push((oi, ni));
+            }
+            None => changes.push(Change::Removed { kind, name: old[oi].name.clone(), entity: old[oi].value.clone() }),
+        }
+    }
+    pairs.sort();
+    for (oi, ni) in pairs {
+        let fields = diff_fields(&old[oi], &new[ni]);
+        if !fields.is_empty() {
+            changes.push(Change::Modified { kind, name: old[oi].name.clone(), fields });
+        }
+    }
+    for (ni, n) in new.iter().enumerate() {
+        if !new_matched[ni] {
+            changes.push(Change::Added { kind, name: n.name.clone(), entity: n.value.clone() });
+        }
+    }
+    changes
+}
+
+/// Compare two codeplugs, returning the changes needed to turn old into new
+pub fn diff_codeplugs(opt: &Opt, old: &Codeplug, new: &Codeplug) -> Result<Vec<Change>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    // IDs differ between codeplugs, compare references by name
+    let old = ids::ids_to_names(old);
+    let new = ids::ids_to_names(new);
+    let mut changes = Vec::new();
+    for kind in [EntityKind::Channel, EntityKind::Zone, EntityKind::ScanList, EntityKind::Talkgroup, EntityKind::TalkgroupList] {
+        changes.extend(diff_kind(kind, entries(kind, &old)?, entries(kind, &new)?));
+    }
+    Ok(changes)
+}
+
+/// Print changes, one entity per line with indented field changes
+pub fn print_changes(opt: &Opt, changes: &[Change]) {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let show = |v: &Option<String>| v.clone().unwrap_or("(unset)".to_string());
+    for change in changes {
+        match change {
+            Change::Added { kind, name, .. } => {
+                uprintln!(opt, Stdout, Color::Green, None, "+ {} {}", kind, name);
+            }
+            Change::Removed { kind, name, .. } => {
+                uprintln!(opt, Stdout, Color::Red, None, "- {} {}", kind, name);
+            }
+            Change::Modified { kind, name, fields } => {
+                uprintln!(opt, Stdout, Color::Yellow, None, "~ {} {}", kind, name);
+                for field in fields {
+                    uprintln!(opt, Stdout, None, None, "    {}: {} -> {}", field.field, show(&field.old), show(&field.new));
+                }
+            }
+        }
+    }
+    let count = |f: fn(&Change) -> bool| changes.iter().filter(|c| f(c)).count();
+    if !opt.quiet { uprintln!(opt, Stderr, Color::Cyan, None, "Diff: {} added, {} removed, {} modified",
+        count(|c| matches!(c, Change::Added { .. })),
+        count(|c| matches!(c, Change::Removed { .. })),
+        count(|c| matches!(c, Change::Modified { .. }))); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rust_decimal::Decimal;
+
+    fn channel(index: usize, name: &str, frequency: i64) -> Channel {
+        Channel {
+            index,
+            uid: format!("ch{}", index),
+            name: name.to_string(),
+            frequency_rx: Decimal::new(frequency, 0),
+            frequency_tx: Decimal::new(frequency, 0),
+            ..Default::default()
+        }
+    }
+
+    fn zone(index: usize, name: &str, channels: &[&str]) -> Zone {
+        Zone {
+            index,
+            uid: format!("zone{}", index),
+            name: name.to_string(),
+            channels: channels.iter().map(|c| c.to_string()).collect(),
+            a_channel: None,
+            b_channel: None,
+            hidden: None,
+            extensions: None,
+        }
+    }
+
+    fn scanlist(index: usize, name: &str, channels: &[&str]) -> ScanList {
+        ScanList {
+            index,
+            uid: format!("sl{}", index),
+            name: name.to_string(),
+            channels: channels.iter().map(|c| c.to_string()).collect(),
+            scan_mode: None,
+            priority_channel_1: None,
+            priority_channel_2: None,
+            revert_channel: None,
+            look_back_time_a: None,
+            look_back_time_b: None,
+            dropout_delay_time: None,
+            dwell_time: None,
+            extensions: None,
+        }
+    }
+
+    fn diff(old: &Codeplug, new: &Codeplug) -> Vec<Change> {
+        diff_codeplugs(&Opt::parse_from(["plungle", "-q"]), old, new).unwrap()
+    }
+
+    #[test]
+    fn reindexed_entities_are_unchanged() {
+        let old = Codeplug {
+            channels: vec![channel(1, "Simplex", 146_520_000), channel(2, "Repeater", 147_000_000)],
+            zones: vec![zone(1, "VHF", &["ch1", "ch2"])],
+            ..Default::default()
+        };
+        let new = Codeplug {
+            channels: vec![channel(1, "Repeater", 147_000_000), channel(2, "Simplex", 146_520_000)],
+            zones: vec![zone(5, "VHF", &["ch2", "ch1"])],
+            ..Default::default()
+        };
+        assert_eq!(diff(&old, &new), vec![]);
+    }
+
+    #[test]
+    fn renamed_channel_is_matched_by_frequency() {
+        let old = Codeplug { channels: vec![channel(1, "Simplex", 146_520_000)], ..Default::default() };
+        let new = Codeplug { channels: vec![channel(1, "Calling", 146_520_000)], ..Default::default() };
+        let changes = diff(&old, &new);
+        let fields = vec![FieldChange { field: "name".to_string(), old: Some("Simplex".to_string()), new: Some("Calling".to_string()) }];
+        assert_eq!(changes, vec![Change::Modified { kind: EntityKind::Channel, name: "Simplex".to_string(), fields }]);
+    }
+
+    #[test]
+    fn renamed_zone_is_removed_and_added() {
+        let old = Codeplug { zones: vec![zone(1, "VHF", &[])], ..Default::default() };
+        let new = Codeplug { zones: vec![zone(1, "2m", &[])], ..Default::default() };
+        let changes = diff(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(&changes[0], Change::Removed { kind: EntityKind::Zone, name, .. } if name == "VHF"));
+        assert!(matches!(&changes[1], Change::Added { kind: EntityKind::Zone, name, .. } if name == "2m"));
+    }
+
+    #[test]
+    fn scan_list_references_follow_channel_renames() {
+        let mut old = Codeplug {
+            channels: vec![channel(1, "Simplex", 146_520_000)],
+            scanlists: vec![scanlist(1, "VHF", &["ch1"])],
+            ..Default::default()
+        };
+        old.scanlists[0].priority_channel_1 = Some(ScanListChannel::Channel("ch1".to_string()));
+        let mut new = old.clone();
+        new.channels[0].name = "Calling".to_string();
+        let changes = diff(&old, &new);
+        assert_eq!(changes.len(), 2);
+        let Change::Modified { kind: EntityKind::ScanList, fields, .. } = &changes[1] else {
+            panic!("expected a modified scan list: {:?}", changes[1]);
+        };
+        let field = |name: &str| fields.iter().find(|f| f.field == name).and_then(|f| f.new.clone());
+        assert_eq!(field("channels"), Some("Calling".to_string()));
+        assert_eq!(field("priority_channel_1.Channel"), Some("Calling".to_string()));
+    }
+}
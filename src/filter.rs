@@ -110,8 +110,8 @@ fn filter_zones(opt: &Opt, source_codeplug: &structures::Codeplug, dest_codeplug
         for zone in &source_codeplug.zones {
             // check if the zone has any channels in the destination codeplug
             let mut has_channel = false;
-            for channel_name in &zone.channels {
-                if dest_codeplug.channels.iter().any(|c| c.name == *channel_name) {
+            for channel_uid in &zone.channels {
+                if dest_codeplug.channels.iter().any(|c| c.uid == *channel_uid) {
                     has_channel = true;
                     break;
                 }
@@ -173,8 +173,8 @@ fn filter_talkgroups(opt: &Opt, source_codeplug: &structures::Codeplug, dest_cod
             let mut uses_talkgroup = false;
             for channel in &dest_codeplug.channels {
                 if let Some(dmr) = &channel.dmr {
-                    if let Some(tg_name) = &dmr.talkgroup {
-                        if *tg_name == talkgroup.name {
+                    if let Some(tg_uid) = &dmr.talkgroup {
+                        if *tg_uid == talkgroup.uid {
                             uses_talkgroup = true;
                             break;
                         }
@@ -240,8 +240,8 @@ fn filter_talkgroup_lists(opt: &Opt, source_codeplug: &structures::Codeplug, des
                 // if the channel is dmr
                 if let Some(dmr) = &channel.dmr {
                     // check if the talkgroup list is in the channel's rx group list
-                    if let Some(tg_list_name) = &dmr.talkgroup_list {
-                        if *tg_list_name == talkgroup_list.name {
+                    if let Some(tg_list_uid) = &dmr.talkgroup_list {
+                        if *tg_list_uid == talkgroup_list.uid {
                             uses_talkgroup_list = true;
                             break;
                         }
@@ -254,7 +254,7 @@ fn filter_talkgroup_lists(opt: &Opt, source_codeplug: &structures::Codeplug, des
                 // then make sure all the talkgroups in the talkgroup list are in the destination codeplug
                 for talkgroup in &talkgroup_list.talkgroups {
                     // if the talkgroup is not in the destination codeplug, add it
-                    if !dest_codeplug.talkgroups.iter().any(|tg| tg.name == talkgroup.name) {
+                    if !dest_codeplug.talkgroups.iter().any(|tg| tg.uid == talkgroup.uid) {
                         dest_codeplug.talkgroups.push(talkgroup.clone());
                     }
                 }
@@ -10,7 +10,11 @@ pub enum Format {
     Default,
     Json,
     Toml,
+    Yaml,
     Text,
+    Csv,
+    Html,
+    Markdown,
 }
 
 impl std::fmt::Display for Format {
@@ -19,7 +23,11 @@ fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             Format::Default => write!(f, "default"),
             Format::Json => write!(f, "JSON"),
             Format::Toml => write!(f, "TOML"),
+            Format::Yaml => write!(f, "YAML"),
             Format::Text => write!(f, "text"),
+            Format::Csv => write!(f, "CSV"),
+            Format::Html => write!(f, "HTML"),
+            Format::Markdown => write!(f, "Markdown"),
         }
     }
 }
@@ -0,0 +1,156 @@
+// src/ids.rs
+
+use std::collections::{HashMap, HashSet};
+use crate::structures::*;
+
+// Stable entity IDs:
+// Channels, zones, scan lists, talkgroups and talkgroup lists each have a uid, and references
+// between entities (zone and scan list members, channel scan lists, talkgroups and talkgroup lists)
+// hold uids rather than names. Names are display-only.
+// Radios reference entities by name, so readers and writers convert at the edges:
+// - names_to_ids() after a radio is parsed
+// - ids_to_names() before a radio is generated
+
+pub type IdMap = HashMap<String, String>;
+
+// Build a uid from a prefix and index, bumping the index until the uid is unused
+fn next_uid(prefix: &str, index: usize, used: &mut HashSet<String>) -> String {
+    let mut ii = index;
+    loop {
+        let uid = format!("{}{}", prefix, ii);
+        if used.insert(uid.clone()) {
+            return uid;
+        }
+        ii += 1;
+    }
+}
+
+// Assign a uid to every entity that does not have one
+fn assign<'a>(prefix: &str, entities: impl Iterator<Item = (usize, &'a mut String)>) {
+    let entities: Vec<(usize, &mut String)> = entities.collect();
+    let mut used: HashSet<String> = entities.iter()
+        .filter(|(_, uid)| !uid.is_empty())
+        .map(|(_, uid)| uid.to_string())
+        .collect();
+    for (index, uid) in entities {
+        if uid.is_empty() {
+            *uid = next_uid(prefix, index, &mut used);
+        }
+    }
+}
+
+// Map keys to values, the first entity with a given key wins
+fn first_map<'a>(pairs: impl Iterator<Item = (&'a String, &'a String)>) -> IdMap {
+    let mut map = IdMap::new();
+    for (key, value) in pairs {
+        map.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+    map
+}
+
+fn remap(map: &IdMap, key: &mut String) {
+    if let Some(value) = map.get(key) {
+        *key = value.clone();
+    }
+}
+
+/// Rewrite every entity reference in a codeplug through the given maps, unknown references are left alone
+pub fn rewrite_refs(codeplug: &mut Codeplug, channels: &IdMap, scanlists: &IdMap, talkgroups: &IdMap, talkgroup_lists: &IdMap) {
+    for channel in &mut codeplug.channels {
+        if let Some(Scan::ScanList(scanlist)) = &mut channel.scan {
+            remap(scanlists, scanlist);
+        }
+        if let Some(dmr) = &mut channel.dmr {
+            if let Some(talkgroup) = &mut dmr.talkgroup {
+                remap(talkgroups, talkgroup);
+            }
+            if let Some(talkgroup_list) = &mut dmr.talkgroup_list {
+                remap(talkgroup_lists, talkgroup_list);
+            }
+        }
+    }
+    for zone in &mut codeplug.zones {
+        for channel in &mut zone.channels {
+            remap(channels, channel);
+        }
+        if let Some(channel) = &mut zone.a_channel {
+            remap(channels, channel);
+        }
+        if let Some(channel) = &mut zone.b_channel {
+            remap(channels, channel);
+        }
+    }
+    for scanlist in &mut codeplug.scanlists {
+        for channel in &mut scanlist.channels {
+            remap(channels, channel);
+        }
+        if let Some(ScanListChannel::Channel(channel)) = &mut scanlist.priority_channel_1 {
+            remap(channels, channel);
+        }
+        if let Some(ScanListChannel::Channel(channel)) = &mut scanlist.priority_channel_2 {
+            remap(channels, channel);
+        }
+        if let Some(ScanRevert::Channel(channel)) = &mut scanlist.revert_channel {
+            remap(channels, channel);
+        }
+    }
+}
+
+/// Assign uids to all entities and convert name references to uid references
+pub fn names_to_ids(codeplug: &mut Codeplug) {
+    assign("ch", codeplug.channels.iter_mut().map(|c| (c.index, &mut c.uid)));
+    assign("zone", codeplug.zones.iter_mut().map(|z| (z.index, &mut z.uid)));
+    assign("sl", codeplug.scanlists.iter_mut().map(|s| (s.index, &mut s.uid)));
+    assign("tg", codeplug.talkgroups.iter_mut().map(|t| (t.index, &mut t.uid)));
+    assign("tgl", codeplug.talkgroup_lists.iter_mut().map(|t| (t.index, &mut t.uid)));
+    let channels = first_map(codeplug.channels.iter().map(|c| (&c.name, &c.uid)));
+    let scanlists = first_map(codeplug.scanlists.iter().map(|s| (&s.name, &s.uid)));
+    let talkgroups = first_map(codeplug.talkgroups.iter().map(|t| (&t.name, &t.uid)));
+    let talkgroup_lists = first_map(codeplug.talkgroup_lists.iter().map(|t| (&t.name, &t.uid)));
+    // talkgroups in talkgroup lists are copies, give them the uid of the talkgroup they copy
+    for talkgroup_list in &mut codeplug.talkgroup_lists {
+        for talkgroup in &mut talkgroup_list.talkgroups {
+            if let Some(uid) = talkgroups.get(&talkgroup.name) {
+                talkgroup.uid = uid.clone();
+            }
+        }
+    }
+    rewrite_refs(codeplug, &channels, &scanlists, &talkgroups, &talkgroup_lists);
+}
+
+/// Return a copy of the codeplug with uid references converted back to names, for radios and display
+pub fn ids_to_names(codeplug: &Codeplug) -> Codeplug {
+    let mut codeplug = codeplug.clone();
+    let channels = first_map(codeplug.channels.iter().map(|c| (&c.uid, &c.name)));
+    let scanlists = first_map(codeplug.scanlists.iter().map(|s| (&s.uid, &s.name)));
+    let talkgroups = first_map(codeplug.talkgroups.iter().map(|t| (&t.uid, &t.name)));
+    let talkgroup_lists = first_map(codeplug.talkgroup_lists.iter().map(|t| (&t.uid, &t.name)));
+    rewrite_refs(&mut codeplug, &channels, &scanlists, &talkgroups, &talkgroup_lists);
+    codeplug
+}
+
+/// Map uids from an input codeplug onto a target codeplug when merging. Entities that are matched by
+/// name map onto the existing uid, new entities keep their uid unless it is already taken.
+/// Both iterators yield (uid, name) pairs.
+pub fn merge_uids<'a>(prefix: &str,
+    input: impl Iterator<Item = (&'a String, &'a String)>,
+    target: impl Iterator<Item = (&'a String, &'a String)>) -> IdMap {
+    let mut existing = IdMap::new();
+    let mut used = HashSet::new();
+    for (uid, name) in target {
+        existing.entry(name.clone()).or_insert_with(|| uid.clone());
+        used.insert(uid.clone());
+    }
+    let mut map = IdMap::new();
+    for (uid, name) in input {
+        let new_uid = match existing.get(name) {
+            Some(existing_uid) => existing_uid.clone(),
+            None if used.insert(uid.clone()) => uid.clone(),
+            None => next_uid(prefix, 1, &mut used),
+        };
+        // later input entities with the same name are skipped by the merge, map them here too
+        existing.entry(name.clone()).or_insert_with(|| new_uid.clone());
+        map.entry(uid.clone()).or_insert(new_uid);
+    }
+    map
+}
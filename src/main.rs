@@ -18,6 +18,13 @@
 mod printer;
 mod merge;
 mod filter;
+mod ids;
+mod migrate;
+mod csvdir;
+mod report;
+mod cards;
+mod diff;
+mod patch;
 
 #[derive(Debug, Parser)]
 #[clap(version, author, about = "Codeplug conversion tool")]
@@ -66,44 +73,130 @@ enum Commands {
         input: PathBuf,
         /// Output path
         output: PathBuf,
+        /// Generate even if validation finds errors
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    /// Validate a codeplug for a radio, exiting non-zero if there are complaints at or above --fail-on
+    Validate {
+        /// Radio model
+        model: String,
+        /// Input path
+        input: PathBuf,
+        /// Output path (stdout is used if not specified, text output always goes to stderr)
+        output: Option<PathBuf>,
+        /// Lowest severity that fails validation
+        #[arg(long, default_value_t, value_enum)]
+        fail_on: validate::FailOn,
+        /// Output format
+        #[arg(long, default_value_t, value_enum)]
+        output_format: validate::OutputFormat,
     },
     /// Merge codeplugs
     Merge {
         /// Input paths
         inputs: Vec<PathBuf>,
     },
+    /// Compare two codeplugs, matching entities by name (or frequency and mode) rather than index
+    Diff {
+        /// Old codeplug path
+        old: PathBuf,
+        /// New codeplug path
+        new: PathBuf,
+        /// Radio model of the old codeplug, if it is a radio export rather than an intermediary file
+        #[arg(long)]
+        old_model: Option<String>,
+        /// Radio model of the new codeplug, if it is a radio export rather than an intermediary file
+        #[arg(long)]
+        new_model: Option<String>,
+        /// Also write the changes as a patch file (JSON, or YAML with a .yaml/.yml extension)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Apply a patch file (from diff) to a codeplug, skipping changes that conflict with local edits
+    Patch {
+        /// Input path
+        input: PathBuf,
+        /// Patch path
+        patch: PathBuf,
+        /// Output path (stdout is used if not specified)
+        output: Option<PathBuf>,
+    },
+    /// Generate printable pocket reference cards (HTML) for each zone
+    Cards {
+        /// Input path
+        input: PathBuf,
+        /// Output path (stdout is used if not specified)
+        output: Option<PathBuf>,
+        /// Target radio model, names are truncated to its display width (defaults to the codeplug source)
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Page size
+        #[arg(long, default_value_t, value_enum)]
+        page_size: cards::PageSize,
+        /// Font size (points)
+        #[arg(long, default_value_t = 8.0)]
+        font_size: f64,
+    },
+    /// Print the JSON Schema for the intermediary format
+    Schema {
+        /// Output path (stdout is used if not specified)
+        output: Option<PathBuf>,
+    },
 }
 
 fn read_codeplug(opt: &Opt, input_path: &PathBuf) -> Result<structures::Codeplug, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     // if we recognize the file extension, use it to determine the file format
-    // otherwise, use --format (which defaults to JSON)
-    let format = match input_path.extension() {
-        Some(ext) => {
-            match ext.to_str().unwrap() {
-                "json" => helpers::Format::Json,
-                "toml" => helpers::Format::Toml,
-                _ => opt.format.clone(),
+    // a directory is a CSV codeplug, otherwise, use --format (which defaults to JSON)
+    let format = if input_path.is_dir() {
+        helpers::Format::Csv
+    } else {
+        match input_path.extension() {
+            Some(ext) => {
+                match ext.to_str().unwrap() {
+                    "json" => helpers::Format::Json,
+                    "toml" => helpers::Format::Toml,
+                    "yaml" | "yml" => helpers::Format::Yaml,
+                    _ => opt.format.clone(),
+                }
             }
+            None => opt.format.clone(),
         }
-        None => opt.format.clone(),
     };
-    // read the codeplug
-    let codeplug: structures::Codeplug;
+    // read the codeplug, untyped so that older schema versions can be migrated
+    let mut value: serde_json::Value;
     if format == helpers::Format::Json {
         uprintln!(opt, Stderr, Color::Green, None, "Reading codeplug as JSON from: {:?}", input_path);
-        codeplug = serde_json::from_str(&std::fs::read_to_string(input_path)?)?;
+        value = serde_json::from_str(&std::fs::read_to_string(input_path)?)?;
     } else if format == helpers::Format::Toml {
         uprintln!(opt, Stderr, Color::Green, None, "Reading codeplug as TOML from: {:?}", input_path);
-        codeplug = toml::from_str(&std::fs::read_to_string(input_path)?)?;
+        value = toml::from_str(&std::fs::read_to_string(input_path)?)?;
+    } else if format == helpers::Format::Yaml {
+        uprintln!(opt, Stderr, Color::Green, None, "Reading codeplug as YAML from: {:?}", input_path);
+        value = serde_yaml_ng::from_str(&std::fs::read_to_string(input_path)?)?;
+    } else if format == helpers::Format::Csv {
+        uprintln!(opt, Stderr, Color::Green, None, "Reading codeplug as CSV from: {:?}", input_path);
+        value = csvdir::read(opt, input_path)?;
     } else {
         uprintln!(opt, Stderr, Color::Red, None, "Unsupported codeplug format");
         return Err("Unsupported codeplug format".into());
     }
+    migrate::migrate(opt, &mut value)?;
+    let codeplug: structures::Codeplug = serde_json::from_value(value)?;
 
     Ok(codeplug)
 }
 
+// read an intermediary file, or parse a radio export if the model is given
+fn load_codeplug(opt: &Opt, path: &PathBuf, model: &Option<String>) -> Result<structures::Codeplug, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    match model {
+        Some(model) => radios::parse_codeplug(opt, model, path),
+        None => read_codeplug(opt, path),
+    }
+}
+
 fn write_codeplug(opt: &Opt, output_path: &Option<PathBuf>, codeplug: &structures::Codeplug) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     // if --format is Default, and we recognize the file extension, use it to determine the file format
@@ -111,13 +204,17 @@ fn write_codeplug(opt: &Opt, output_path: &Option<PathBuf>, codeplug: &structure
     let format = match opt.format {
         helpers::Format::Default => {
             match output_path {
+                Some(path) if path.is_dir() => helpers::Format::Csv,
                 Some(path) => {
                     match path.extension() {
                         Some(ext) => {
                             match ext.to_str().unwrap() {
                                 "json" => helpers::Format::Json,
                                 "toml" => helpers::Format::Toml,
+                                "yaml" | "yml" => helpers::Format::Yaml,
                                 "txt" => helpers::Format::Text,
+                                "html" | "htm" => helpers::Format::Html,
+                                "md" => helpers::Format::Markdown,
                                 _ => opt.format.clone(),
                             }
                         }
@@ -129,21 +226,40 @@ fn write_codeplug(opt: &Opt, output_path: &Option<PathBuf>, codeplug: &structure
         },
         _ => opt.format.clone(),
     };
-    // serialize the codeplug to a string
-    let file_str = match format {
-        helpers::Format::Json => serde_json::to_string_pretty(codeplug)?,
-        helpers::Format::Toml => toml::to_string_pretty(codeplug)?,
-        helpers::Format::Text => printer::pretty(opt, codeplug)?,
-        helpers::Format::Default => printer::pretty(opt, codeplug)?,
-    };
-
-    // write to file or stdout
-    if output_path.is_none() {
-        if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing codeplug to stdout (--format={})", format); }
-        uprintln!(opt, Stdout, None, None, "{}", file_str);
+    // stamp the schema version
+    let mut codeplug = codeplug.clone();
+    codeplug.schema_version = migrate::SCHEMA_VERSION;
+    let codeplug = &codeplug;
+    if format == helpers::Format::Csv {
+        // CSV is a directory of files rather than a single file
+        let Some(path) = output_path else {
+            uprintln!(opt, Stderr, Color::Red, None, "CSV format requires an output directory");
+            return Err("CSV format requires an output directory".into());
+        };
+        if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing codeplug to {:?} (--format={})", path, format); }
+        csvdir::write(opt, path, codeplug)?;
     } else {
-        if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing codeplug to {:?} (--format={})", output_path.as_ref().unwrap(), format); }
-        std::fs::write(output_path.as_ref().unwrap(), file_str)?;
+        // serialize the codeplug to a string
+        let file_str = match format {
+            helpers::Format::Json => serde_json::to_string_pretty(codeplug)?,
+            helpers::Format::Toml => toml::to_string_pretty(codeplug)?,
+            // through a JSON value so that enums are plain maps rather than YAML tags
+            helpers::Format::Yaml => serde_yaml_ng::to_string(&serde_json::to_value(codeplug)?)?,
+            helpers::Format::Text => printer::pretty(opt, codeplug)?,
+            helpers::Format::Default => printer::pretty(opt, codeplug)?,
+            helpers::Format::Html => report::html(opt, codeplug)?,
+            helpers::Format::Markdown => report::markdown(opt, codeplug)?,
+            helpers::Format::Csv => unreachable!(),
+        };
+
+        // write to file or stdout
+        if output_path.is_none() {
+            if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing codeplug to stdout (--format={})", format); }
+            uprintln!(opt, Stdout, None, None, "{}", file_str);
+        } else {
+            if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing codeplug to {:?} (--format={})", output_path.as_ref().unwrap(), format); }
+            std::fs::write(output_path.as_ref().unwrap(), file_str)?;
+        }
     }
 
     if !opt.quiet { uprintln!(opt, Stderr, Color::Cyan, None, "Codeplug has {} channels, {} zones, {} talkgroups, {} talkgroup lists",
@@ -168,15 +284,69 @@ fn main() -> Result<(), Box<dyn Error>> {
             // write intermediary file
             write_codeplug(&opt, &output, &codeplug)?;
         }
-        Some(Commands::Generate { model, input, output }) => {
+        Some(Commands::Generate { model, input, output, force }) => {
             // read intermediary file
             let mut codeplug = read_codeplug(&opt, &input)?;
             // filter codeplug
             codeplug = filter::filter_codeplug(&opt, &codeplug, &opt.filter)?;
-            // validate codeplug
-            validate::validate_codeplug(&opt, &codeplug, &model)?;
+            // check the output path first, so a refused run doesn't overwrite the log of a previous one
+            if output.exists() && !(output.is_dir() && std::fs::read_dir(output)?.next().is_none()) {
+                uprintln!(opt, Stderr, Color::Red, None, "Output path exists and is not empty, not overwriting!");
+                return Err("Bad output path".into());
+            }
+            // validate codeplug, and keep a log next to the output
+            let complaints = validate::validate_codeplug(&opt, &codeplug, model)?;
+            let mut log_name = output.file_name().ok_or("Invalid output path")?.to_os_string();
+            log_name.push(".validation.log");
+            let log_path = output.with_file_name(log_name);
+            let write_log = || -> Result<(), Box<dyn Error>> {
+                // the output directory may not exist if nothing was generated, so create its parent
+                if let Some(parent) = log_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing validation log to {:?}", log_path); }
+                std::fs::write(&log_path, validate::complaints_text(&complaints))?;
+                Ok(())
+            };
+            // don't generate a codeplug with errors unless forced
+            if validate::fails(&complaints, validate::FailOn::Error) {
+                if !force {
+                    write_log()?;
+                    uprintln!(opt, Stderr, Color::Red, None, "Validation found errors, not generating (use --force to generate anyway)");
+                    return Err("Validation failed".into());
+                }
+                uprintln!(opt, Stderr, Color::Yellow, None, "Validation found errors, generating anyway (--force)");
+            }
             // generate codeplug
             radios::generate_codeplug(&opt, &codeplug, &model, &output)?;
+            write_log()?;
+        }
+        Some(Commands::Validate { model, input, output, fail_on, output_format }) => {
+            // read intermediary file
+            let mut codeplug = read_codeplug(&opt, input)?;
+            // filter codeplug
+            codeplug = filter::filter_codeplug(&opt, &codeplug, &opt.filter)?;
+            // validate codeplug
+            let properties = radios::get_properties(&opt, model)?;
+            let complaints = validate::check_codeplug(&opt, &codeplug, Some(&properties))?;
+            let report_str = match output_format {
+                validate::OutputFormat::Text => None,
+                validate::OutputFormat::Json => Some(validate::complaints_json(&complaints, input, model)?),
+                validate::OutputFormat::Sarif => Some(validate::complaints_sarif(&complaints, input, model)?),
+            };
+            match (report_str, output) {
+                (None, _) => if !opt.quiet { validate::print_complaints(&opt, &complaints) },
+                (Some(report_str), Some(path)) => {
+                    if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing validation results to {:?}", path); }
+                    std::fs::write(path, report_str)?;
+                }
+                (Some(report_str), None) => uprintln!(opt, Stdout, None, None, "{}", report_str),
+            }
+            // exit status for CI
+            if validate::fails(&complaints, *fail_on) {
+                uprintln!(opt, Stderr, Color::Red, None, "Validation failed");
+                std::process::exit(1);
+            }
         }
         Some(Commands::Merge { inputs }) => {
             // merge codeplugs
@@ -184,6 +354,67 @@ fn main() -> Result<(), Box<dyn Error>> {
             // write intermediary file
             write_codeplug(&opt, &None, &codeplug)?; // @TODO FIXME
         }
+        Some(Commands::Diff { old, new, old_model, new_model, output }) => {
+            // read both codeplugs
+            let old_codeplug = filter::filter_codeplug(&opt, &load_codeplug(&opt, old, old_model)?, &opt.filter)?;
+            let new_codeplug = filter::filter_codeplug(&opt, &load_codeplug(&opt, new, new_model)?, &opt.filter)?;
+            // compare
+            let changes = diff::diff_codeplugs(&opt, &old_codeplug, &new_codeplug)?;
+            diff::print_changes(&opt, &changes);
+            // write patch file
+            if let Some(path) = output {
+                patch::write_patch(&opt, path, &changes)?;
+            }
+        }
+        Some(Commands::Patch { input, patch, output }) => {
+            // read intermediary file and patch
+            let codeplug = read_codeplug(&opt, input)?;
+            let patch = patch::read_patch(&opt, patch)?;
+            // apply the patch
+            let (codeplug, conflicts) = patch::apply_patch(&opt, &codeplug, &patch)?;
+            for conflict in &conflicts {
+                uprintln!(opt, Stderr, Color::Yellow, None, "Conflict: {}", conflict);
+            }
+            if !opt.quiet { uprintln!(opt, Stderr, Color::Cyan, None, "Patch: {} changes, {} conflicts", patch.changes.len(), conflicts.len()); }
+            // write intermediary file
+            write_codeplug(&opt, output, &codeplug)?;
+        }
+        Some(Commands::Cards { input, output, model, page_size, font_size }) => {
+            // read intermediary file
+            let mut codeplug = read_codeplug(&opt, input)?;
+            // filter codeplug
+            codeplug = filter::filter_codeplug(&opt, &codeplug, &opt.filter)?;
+            // abbreviate names to fit the target radio's display
+            let properties = match model {
+                Some(model) => Some(radios::get_properties(&opt, model)?),
+                None => radios::find_properties(&codeplug.source),
+            };
+            let options = cards::CardOptions {
+                page_size: page_size.clone(),
+                font_size: *font_size,
+                name_width: properties.map(|p| p.channel_name_width_max),
+            };
+            let cards_str = cards::cards(&opt, &codeplug, &options)?;
+            match output {
+                Some(path) => {
+                    if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing cards to {:?}", path); }
+                    std::fs::write(path, cards_str)?;
+                }
+                None => uprintln!(opt, Stdout, None, None, "{}", cards_str),
+            }
+        }
+        Some(Commands::Schema { output }) => {
+            // derive the schema from the intermediary structures
+            let schema = schemars::schema_for!(structures::Codeplug);
+            let schema_str = serde_json::to_string_pretty(&schema)?;
+            match output {
+                Some(path) => {
+                    if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing schema to {:?}", path); }
+                    std::fs::write(path, schema_str)?;
+                }
+                None => uprintln!(opt, Stdout, None, None, "{}", schema_str),
+            }
+        }
         None => { // this should never happen because of arg_required_else_help
             uprintln!(opt, Stderr, Color::Red, None, "No command specified");
         }
@@ -4,6 +4,22 @@
 
 fn merge_all(opt: &Opt, input_codeplug: &structures::Codeplug, target_codeplug: &mut structures::Codeplug) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    // map the input IDs onto the target codeplug, and rewrite the input references to match
+    let channel_uids = ids::merge_uids("ch",
+        input_codeplug.channels.iter().map(|c| (&c.uid, &c.name)),
+        target_codeplug.channels.iter().map(|c| (&c.uid, &c.name)));
+    let zone_uids = ids::merge_uids("zone",
+        input_codeplug.zones.iter().map(|z| (&z.uid, &z.name)),
+        target_codeplug.zones.iter().map(|z| (&z.uid, &z.name)));
+    let talkgroup_uids = ids::merge_uids("tg",
+        input_codeplug.talkgroups.iter().map(|t| (&t.uid, &t.name)),
+        target_codeplug.talkgroups.iter().map(|t| (&t.uid, &t.name)));
+    let talkgroup_list_uids = ids::merge_uids("tgl",
+        input_codeplug.talkgroup_lists.iter().map(|t| (&t.uid, &t.name)),
+        target_codeplug.talkgroup_lists.iter().map(|t| (&t.uid, &t.name)));
+    let mut input_codeplug = input_codeplug.clone();
+    // scan lists are not merged, so scan list references are left as-is
+    ids::rewrite_refs(&mut input_codeplug, &channel_uids, &ids::IdMap::new(), &talkgroup_uids, &talkgroup_list_uids);
     // merge channels
     // find the highest channel index in the target codeplug
     let mut max_channel_index: usize = 0;
@@ -21,6 +37,7 @@ fn merge_all(opt: &Opt, input_codeplug: &structures::Codeplug, target_codeplug:
             let mut new_channel = channel.clone();
             max_channel_index += 1;
             new_channel.index = max_channel_index;
+            new_channel.uid = channel_uids[&channel.uid].clone();
             // add the channel to the target codeplug
             target_codeplug.channels.push(new_channel);
         }
@@ -42,6 +59,7 @@ fn merge_all(opt: &Opt, input_codeplug: &structures::Codeplug, target_codeplug:
             let mut new_zone = zone.clone();
             max_zone_index += 1;
             new_zone.index = max_zone_index;
+            new_zone.uid = zone_uids[&zone.uid].clone();
             // add the zone to the target codeplug
             target_codeplug.zones.push(new_zone);
         }
@@ -63,6 +81,7 @@ fn merge_all(opt: &Opt, input_codeplug: &structures::Codeplug, target_codeplug:
             let mut new_talkgroup = talkgroup.clone();
             max_talkgroup_index += 1;
             new_talkgroup.index = max_talkgroup_index;
+            new_talkgroup.uid = talkgroup_uids[&talkgroup.uid].clone();
             // add the talkgroup to the target codeplug
             target_codeplug.talkgroups.push(new_talkgroup);
         }
@@ -84,10 +103,50 @@ fn merge_all(opt: &Opt, input_codeplug: &structures::Codeplug, target_codeplug:
             let mut new_talkgroup_list = talkgroup_list.clone();
             max_talkgroup_list_index += 1;
             new_talkgroup_list.index = max_talkgroup_list_index;
+            new_talkgroup_list.uid = talkgroup_list_uids[&talkgroup_list.uid].clone();
+            for talkgroup in &mut new_talkgroup_list.talkgroups {
+                if let Some(uid) = talkgroup_uids.get(&talkgroup.uid) {
+                    talkgroup.uid = uid.clone();
+                }
+            }
             // add the talkgroup list to the target codeplug
             target_codeplug.talkgroup_lists.push(new_talkgroup_list);
         }
     }
+    // merge contacts
+    // contacts are matched by DMR ID, not callsign
+    for contact in &input_codeplug.contacts {
+        if target_codeplug.contacts.iter().any(|c| c.id == contact.id) {
+            uprintln!(opt, Stderr, Color::Yellow, None, "Contact already exists in codeplug, skipping: {:8} {}",
+                contact.id, contact.callsign);
+        } else {
+            let mut new_contact = contact.clone();
+            new_contact.index = target_codeplug.contacts.len() + 1;
+            target_codeplug.contacts.push(new_contact);
+        }
+    }
+    // merge broadcast and airband memories
+    // memories are matched by frequency, not name (names are optional)
+    for memory in &input_codeplug.broadcast_memories {
+        if target_codeplug.broadcast_memories.iter().any(|m| m.frequency == memory.frequency) {
+            uprintln!(opt, Stderr, Color::Yellow, None, "Broadcast memory already exists in codeplug, skipping: {:4} {}",
+                memory.index, freq2str(&memory.frequency));
+        } else {
+            let mut new_memory = memory.clone();
+            new_memory.index = target_codeplug.broadcast_memories.len() + 1;
+            target_codeplug.broadcast_memories.push(new_memory);
+        }
+    }
+    for memory in &input_codeplug.airband_memories {
+        if target_codeplug.airband_memories.iter().any(|m| m.frequency == memory.frequency) {
+            uprintln!(opt, Stderr, Color::Yellow, None, "Airband memory already exists in codeplug, skipping: {:4} {}",
+                memory.index, freq2str(&memory.frequency));
+        } else {
+            let mut new_memory = memory.clone();
+            new_memory.index = target_codeplug.airband_memories.len() + 1;
+            target_codeplug.airband_memories.push(new_memory);
+        }
+    }
     Ok(())
 }
 
@@ -0,0 +1,120 @@
+// src/migrate.rs
+
+use std::collections::{HashMap, HashSet};
+use crate::*;
+
+// Intermediary format migrations:
+// Codeplug files carry a schema_version (missing in files written before versioning, read as 0).
+// On read, the file is loaded as an untyped value and each migration upgrades it by one version
+// until it matches SCHEMA_VERSION, then it is deserialized into structures::Codeplug.
+// When a change to structures.rs breaks older files, bump SCHEMA_VERSION and add a migration.
+
+/// Schema version written by this binary
+pub const SCHEMA_VERSION: u32 = 1;
+
+type Migration = fn(&mut serde_json::Value) -> Result<(), Box<dyn Error>>;
+
+// MIGRATIONS[n] upgrades a codeplug from version n to version n + 1
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize] = [
+    migrate_0_to_1,
+];
+
+// Version 0 -> 1: entity IDs
+// Version 0 references entities by name, and is otherwise identical to version 1.
+// This works on the untyped value (rather than structures::Codeplug) so that it keeps working as the
+// structures change, and follows the uid scheme of ids::names_to_ids() as it was in version 1.
+fn migrate_0_to_1(value: &mut serde_json::Value) -> Result<(), Box<dyn Error>> {
+    use serde_json::Value;
+    let tables = [("channels", "ch"), ("zones", "zone"), ("scanlists", "sl"), ("talkgroups", "tg"), ("talkgroup_lists", "tgl")];
+    // assign uids, prefix + index, bumping the index until the uid is unused, and map names to them
+    let mut maps: HashMap<&str, HashMap<String, String>> = HashMap::new();
+    for (table, prefix) in tables {
+        let mut map = HashMap::new();
+        let mut used: HashSet<String> = HashSet::new();
+        for entity in value[table].as_array_mut().into_iter().flatten() {
+            let mut ii = entity["index"].as_u64().ok_or(format!("Invalid index in {}", table))?;
+            let uid = loop {
+                let uid = format!("{}{}", prefix, ii);
+                if used.insert(uid.clone()) {
+                    break uid;
+                }
+                ii += 1;
+            };
+            if let Some(name) = entity["name"].as_str() {
+                map.entry(name.to_string()).or_insert_with(|| uid.clone());
+            }
+            entity["uid"] = Value::from(uid);
+        }
+        maps.insert(table, map);
+    }
+    // rewrite a name reference to a uid, unknown names are left alone
+    let remap = |table: &str, reference: &mut Value| {
+        if let Some(uid) = reference.as_str().and_then(|name| maps[table].get(name)) {
+            *reference = Value::from(uid.clone());
+        }
+    };
+    for channel in value["channels"].as_array_mut().into_iter().flatten() {
+        if let Some(scanlist) = channel.pointer_mut("/scan/ScanList") {
+            remap("scanlists", scanlist);
+        }
+        if let Some(talkgroup) = channel.pointer_mut("/dmr/talkgroup") {
+            remap("talkgroups", talkgroup);
+        }
+        if let Some(talkgroup_list) = channel.pointer_mut("/dmr/talkgroup_list") {
+            remap("talkgroup_lists", talkgroup_list);
+        }
+    }
+    for zone in value["zones"].as_array_mut().into_iter().flatten() {
+        for channel in zone["channels"].as_array_mut().into_iter().flatten() {
+            remap("channels", channel);
+        }
+        for field in ["a_channel", "b_channel"] {
+            if let Some(channel) = zone.get_mut(field) {
+                remap("channels", channel);
+            }
+        }
+    }
+    for scanlist in value["scanlists"].as_array_mut().into_iter().flatten() {
+        for channel in scanlist["channels"].as_array_mut().into_iter().flatten() {
+            remap("channels", channel);
+        }
+        for pointer in ["/priority_channel_1/Channel", "/priority_channel_2/Channel", "/revert_channel/Channel"] {
+            if let Some(channel) = scanlist.pointer_mut(pointer) {
+                remap("channels", channel);
+            }
+        }
+    }
+    // talkgroups in talkgroup lists are copies, give them the uid of the talkgroup they copy
+    for talkgroup_list in value["talkgroup_lists"].as_array_mut().into_iter().flatten() {
+        for talkgroup in talkgroup_list["talkgroups"].as_array_mut().into_iter().flatten() {
+            let uid = talkgroup["name"].as_str().and_then(|name| maps["talkgroups"].get(name)).cloned().unwrap_or_default();
+            talkgroup["uid"] = Value::from(uid);
+        }
+    }
+    Ok(())
+}
+
+/// Upgrade an untyped codeplug to the current schema version
+pub fn migrate(opt: &Opt, value: &mut serde_json::Value) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let version = match value.get("schema_version") {
+        Some(v) => match v.as_u64() {
+            Some(v) => v as u32,
+            None => return Err(format!("Invalid schema_version: {}", v).into()),
+        },
+        None => 0,
+    };
+    if version > SCHEMA_VERSION {
+        uprintln!(opt, Stderr, Color::Red, None, "Codeplug schema version {} is newer than this version of plungle supports ({}), please upgrade plungle",
+            version, SCHEMA_VERSION);
+        return Err("Unsupported codeplug schema version".into());
+    }
+    for from in version..SCHEMA_VERSION {
+        uprintln!(opt, Stderr, None, 1, "Migrating codeplug from schema version {} to {}", from, from + 1);
+        MIGRATIONS[from as usize](value)?;
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), serde_json::Value::from(SCHEMA_VERSION));
+    }
+    Ok(())
+}
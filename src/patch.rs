@@ -0,0 +1,403 @@
+// src/patch.rs
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::*;
+use crate::structures::*;
+use crate::diff::{Change, EntityKind, FieldChange};
+
+// Patches:
+// A patch is the change set produced by `diff`, applied to another codeplug with `patch`.
+// Entities are found by name, and references in the patch are names, so a patch applies to any
+// codeplug that has the same entities regardless of indexes or IDs.
+// Each change (or each field of a modified entity) is checked against the target first: if the
+// target already matches the new value it is skipped, if it doesn't match the old value it is a
+// conflict and is left alone, so local edits are never overwritten.
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Patch {
+    pub changes: Vec<Change>,
+}
+
+fn table(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Channel => "channels",
+        EntityKind::Zone => "zones",
+        EntityKind::ScanList => "scanlists",
+        EntityKind::Talkgroup => "talkgroups",
+        EntityKind::TalkgroupList => "talkgroup_lists",
+    }
+}
+
+/// Write a patch file, as YAML if the extension is .yaml/.yml and JSON otherwise
+pub fn write_patch(opt: &Opt, path: &Path, changes: &[Change]) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let patch = Patch { changes: changes.to_vec() };
+    let patch_str = match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml_ng::to_string(&patch)?,
+        _ => serde_json::to_string_pretty(&patch)?,
+    };
+    if !opt.quiet { uprintln!(opt, Stderr, Color::Green, None, "Writing patch to {:?}", path); }
+    std::fs::write(path, patch_str)?;
+    Ok(())
+}
+
+/// Read a patch file, as YAML if the extension is .yaml/.yml and JSON otherwise
+pub fn read_patch(opt: &Opt, path: &Path) -> Result<Patch, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, Color::Green, None, "Reading patch from: {:?}", path);
+    let patch_str = std::fs::read_to_string(path)?;
+    let patch = match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml_ng::from_str(&patch_str)?,
+        _ => serde_json::from_str(&patch_str)?,
+    };
+    Ok(patch)
+}
+
+fn find(codeplug: &Value, kind: EntityKind, name: &str) -> Option<usize> {
+    codeplug[table(kind)].as_array()?.iter().position(|e| e["name"] == name)
+}
+
+fn current_field(entity: &Value, field: &str) -> Option<String> {
+    let mut fields = Vec::new();
+    csvdir::flatten("", &diff::normalize(entity.clone()), &mut fields);
+    fields.into_iter().find(|(f, _)| f == field).map(|(_, v)| v)
+}
+
+// Rewrite references to a renamed entity, the codeplug references entities by name here
+fn rename_refs(codeplug: &mut Value, kind: EntityKind, old: &str, new: &str) -> Result<(), Box<dyn Error>> {
+    let mut typed: Codeplug = serde_json::from_value(codeplug.clone())?;
+    let map = ids::IdMap::from([(old.to_string(), new.to_string())]);
+    let empty = ids::IdMap::new();
+    match kind {
+        EntityKind::Channel => ids::rewrite_refs(&mut typed, &map, &empty, &empty, &empty),
+        EntityKind::ScanList => ids::rewrite_refs(&mut typed, &empty, &map, &empty, &empty),
+        EntityKind::Talkgroup => ids::rewrite_refs(&mut typed, &empty, &empty, &map, &empty),
+        EntityKind::TalkgroupList => ids::rewrite_refs(&mut typed, &empty, &empty, &empty, &map),
+        EntityKind::Zone => {}, // zones are not referenced
+    }
+    *codeplug = serde_json::to_value(&typed)?;
+    Ok(())
+}
+
+// Remove references to a removed channel (from zones and scan lists) or scan list (from channels)
+fn remove_refs(codeplug: &mut Value, kind: EntityKind, name: &str) {
+    match kind {
+        EntityKind::Channel => for table in ["zones", "scanlists"] {
+            for entity in codeplug[table].as_array_mut().into_iter().flatten() {
+                if let Some(Value::Array(channels)) = entity.get_mut("channels") {
+                    channels.retain(|c| c != name);
+                }
+                for selected in ["a_channel", "b_channel"] {
+                    if entity.get(selected).is_some_and(|c| c == name) {
+                        entity[selected] = Value::Null;
+                    }
+                }
+                for selected in ["priority_channel_1", "priority_channel_2", "revert_channel"] {
+                    if entity.pointer(&format!("/{}/Channel", selected)).is_some_and(|c| c == name) {
+                        entity[selected] = Value::Null;
+                    }
+                }
+            }
+        },
+        EntityKind::ScanList => for channel in codeplug["channels"].as_array_mut().into_iter().flatten() {
+            if channel.pointer("/scan/ScanList").is_some_and(|s| s == name) {
+                channel["scan"] = Value::Null;
+            }
+        },
+        _ => {}, // other references are left, and reported by apply_patch() if they dangle
+    }
+}
+
+// Talkgroup lists hold copies of their talkgroups, refresh them from the talkgroups by name
+fn refresh_talkgroup_lists(codeplug: &mut Value) -> Result<(), Box<dyn Error>> {
+    let talkgroups = codeplug["talkgroups"].as_array().cloned().unwrap_or_default();
+    for talkgroup_list in codeplug["talkgroup_lists"].as_array_mut().into_iter().flatten() {
+        let list_name = talkgroup_list["name"].clone();
+        if let Some(Value::Array(members)) = talkgroup_list.get_mut("talkgroups") {
+            for member in members.iter_mut() {
+                let name = if member.is_object() { member["name"].clone() } else { member.clone() };
+                match talkgroups.iter().find(|tg| tg["name"] == name) {
+                    Some(talkgroup) => *member = talkgroup.clone(),
+                    None if member.is_object() => {}, // not in the talkgroups, keep the copy
+                    None => return Err(format!("Talkgroup list {} references unknown talkgroup: {}", list_name, name).into()),
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_field(schema: &Value, kind: EntityKind, entity: &mut Value, field: &FieldChange) -> Result<(), Box<dyn Error>> {
+    // talkgroup list members are written as names, see refresh_talkgroup_lists()
+    if kind == EntityKind::TalkgroupList && field.field == "talkgroups" {
+        let names = field.new.as_deref().unwrap_or("");
+        entity["talkgroups"] = names.split(',').filter(|n| !n.is_empty()).map(|n| Value::from(n.trim())).collect();
+        return Ok(());
+    }
+    let items = &schema["properties"][table(kind)]["items"];
+    match &field.new {
+        Some(cell) => csvdir::insert(schema, items, entity, &field.field, cell),
+        None => csvdir::remove(schema, items, entity, &field.field),
+    }
+}
+
+// Apply renames made earlier in the patch to a cell (a name, or comma-separated names)
+fn renamed(cell: &Option<String>, renames: &[(String, String)]) -> Option<String> {
+    cell.as_ref().map(|cell| cell.split(',').map(|name| {
+        renames.iter().find(|(old, _)| old == name).map_or(name, |(_, new)| new.as_str())
+    }).collect::<Vec<&str>>().join(","))
+}
+
+// Apply a single change, returning conflicts
+fn apply_change(opt: &Opt, schema: &Value, codeplug: &mut Value, change: &Change, renames: &mut Vec<(String, String)>) -> Result<Vec<String>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let mut conflicts = Vec::new();
+    match change {
+        Change::Added { kind, name, entity } => {
+            if let Some(ii) = find(codeplug, *kind, name) {
+                if diff::normalize(codeplug[table(*kind)][ii].clone()) != *entity {
+                    conflicts.push(format!("Cannot add {} {}: it already exists and is different", kind, name));
+                }
+                return Ok(conflicts);
+            }
+            let entities = codeplug[table(*kind)].as_array_mut().ok_or("Codeplug is missing a table")?;
+            let index = entities.iter().filter_map(|e| e["index"].as_u64()).max().unwrap_or(0) + 1;
+            let mut new_entity = entity.clone();
+            new_entity["index"] = Value::from(index);
+            new_entity["uid"] = Value::from(""); // assigned after the patch is applied
+            entities.push(new_entity);
+        }
+        Change::Removed { kind, name, entity } => {
+            let Some(ii) = find(codeplug, *kind, name) else {
+                return Ok(conflicts); // already removed
+            };
+            if diff::normalize(codeplug[table(*kind)][ii].clone()) != *entity {
+                conflicts.push(format!("Cannot remove {} {}: it has changed", kind, name));
+                return Ok(conflicts);
+            }
+            codeplug[table(*kind)].as_array_mut().unwrap().remove(ii);
+            remove_refs(codeplug, *kind, name);
+        }
+        Change::Modified { kind, name, fields } => {
+            let Some(ii) = find(codeplug, *kind, name) else {
+                conflicts.push(format!("Cannot modify {} {}: not found", kind, name));
+                return Ok(conflicts);
+            };
+            for field in fields {
+                let current = current_field(&codeplug[table(*kind)][ii], &field.field);
+                if current == field.new {
+                    continue; // already applied
+                }
+                // references to entities renamed by this patch have already been followed
+                if current != field.old && current != renamed(&field.old, renames) {
+                    conflicts.push(format!("Cannot modify {} {} field {}: expected {}, found {}", kind, name, field.field,
+                        field.old.as_deref().unwrap_or("(unset)"), current.as_deref().unwrap_or("(unset)")));
+                    continue;
+                }
+                set_field(schema, *kind, &mut codeplug[table(*kind)][ii], field)?;
+                // renames are followed by everything that references the entity
+                if field.field == "name" {
+                    if let (Some(old), Some(new)) = (&field.old, &field.new) {
+                        rename_refs(codeplug, *kind, old, new)?;
+                        renames.push((old.clone(), new.clone()));
+                    }
+                }
+            }
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Apply a patch to a codeplug, returning the patched codeplug and any conflicts (which are not applied,
+/// except for references left dangling, which are reported after the patch is applied)
+pub fn apply_patch(opt: &Opt, codeplug: &Codeplug, patch: &Patch) -> Result<(Codeplug, Vec<String>), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let schema = serde_json::to_value(schemars::schema_for!(Codeplug))?;
+    // patches reference entities by name
+    let mut value = serde_json::to_value(ids::ids_to_names(codeplug))?;
+    let mut conflicts = Vec::new();
+    let mut renames = Vec::new();
+    for change in &patch.changes {
+        conflicts.extend(apply_change(opt, &schema, &mut value, change, &mut renames)?);
+    }
+    refresh_talkgroup_lists(&mut value)?;
+    let mut patched: Codeplug = serde_json::from_value(value)?;
+    // assign IDs to added entities and convert references back to IDs
+    ids::names_to_ids(&mut patched);
+    // references the patch left dangling are conflicts too, the patch is still applied
+    let before = validate::validate_references(opt, codeplug)?;
+    for complaint in validate::validate_references(opt, &patched)? {
+        if !before.contains(&complaint) {
+            conflicts.push(format!("Patch leaves a dangling reference in {}: {}",
+                complaint.source_name.as_deref().unwrap_or("codeplug"), complaint.message));
+        }
+    }
+    Ok((patched, conflicts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rust_decimal::Decimal;
+
+    fn channel(index: usize, name: &str, frequency: i64) -> Channel {
+        Channel {
+            index,
+            uid: format!("ch{}", index),
+            name: name.to_string(),
+            frequency_rx: Decimal::new(frequency, 0),
+            frequency_tx: Decimal::new(frequency, 0),
+            ..Default::default()
+        }
+    }
+
+    fn zone(index: usize, name: &str, channels: &[&str]) -> Zone {
+        Zone {
+            index,
+            uid: format!("zone{}", index),
+            name: name.to_string(),
+            channels: channels.iter().map(|c| c.to_string()).collect(),
+            a_channel: channels.first().map(|c| c.to_string()),
+            b_channel: None,
+            hidden: None,
+            extensions: None,
+        }
+    }
+
+    fn talkgroup(index: usize, name: &str, id: u32) -> DmrTalkgroup {
+        DmrTalkgroup {
+            index,
+            uid: format!("tg{}", index),
+            id,
+            name: name.to_string(),
+            call_type: DmrTalkgroupCallType::Group,
+            alert: false,
+            timeslot_override: None,
+            extensions: None,
+        }
+    }
+
+    fn changes(old: &Codeplug, new: &Codeplug) -> Vec<Change> {
+        diff::diff_codeplugs(&Opt::parse_from(["plungle", "-q"]), old, new).unwrap()
+    }
+
+    fn apply(target: &Codeplug, changes: Vec<Change>) -> (Codeplug, Vec<String>) {
+        apply_patch(&Opt::parse_from(["plungle", "-q"]), target, &Patch { changes }).unwrap()
+    }
+
+    // diff old and new, and apply the result to target
+    fn patch(old: &Codeplug, new: &Codeplug, target: &Codeplug) -> (Codeplug, Vec<String>) {
+        apply(target, changes(old, new))
+    }
+
+    fn base() -> Codeplug {
+        Codeplug {
+            channels: vec![channel(1, "Simplex", 146_520_000), channel(2, "Repeater", 147_000_000)],
+            zones: vec![zone(1, "VHF", &["ch1", "ch2"])],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn modified_field_is_applied_by_name() {
+        let mut new = base();
+        new.channels[1].power = Power::Watts(5.0);
+        // the target has the same channels in a different order
+        let mut target = base();
+        target.channels.reverse();
+        let (patched, conflicts) = patch(&base(), &new, &target);
+        assert_eq!(conflicts, Vec::<String>::new());
+        assert_eq!(patched.channels[0].name, "Repeater");
+        assert_eq!(patched.channels[0].power, Power::Watts(5.0));
+        assert_eq!(patched.channels[1].power, Power::Default);
+    }
+
+    #[test]
+    fn local_edit_is_a_conflict() {
+        let mut new = base();
+        new.channels[1].power = Power::Watts(5.0);
+        let mut target = base();
+        target.channels[1].power = Power::Watts(1.0);
+        let (patched, conflicts) = patch(&base(), &new, &target);
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].starts_with("Cannot modify channel Repeater field power"), "{}", conflicts[0]);
+        assert_eq!(patched.channels[1].power, Power::Watts(1.0));
+    }
+
+    #[test]
+    fn rename_is_followed_by_references() {
+        let mut new = base();
+        new.channels[0].name = "Calling".to_string();
+        let (patched, conflicts) = patch(&base(), &new, &base());
+        assert_eq!(conflicts, Vec::<String>::new());
+        let patched = ids::ids_to_names(&patched);
+        assert_eq!(patched.channels[0].name, "Calling");
+        assert_eq!(patched.zones[0].channels, vec!["Calling", "Repeater"]);
+        assert_eq!(patched.zones[0].a_channel.as_deref(), Some("Calling"));
+    }
+
+    #[test]
+    fn removed_channel_is_removed_from_references() {
+        let mut old = base();
+        old.scanlists = vec![ScanList {
+            index: 1,
+            uid: "sl1".to_string(),
+            name: "VHF".to_string(),
+            channels: vec!["ch1".to_string(), "ch2".to_string()],
+            scan_mode: None,
+            priority_channel_1: Some(ScanListChannel::Channel("ch1".to_string())),
+            priority_channel_2: None,
+            revert_channel: Some(ScanRevert::Channel("ch1".to_string())),
+            look_back_time_a: None,
+            look_back_time_b: None,
+            dropout_delay_time: None,
+            dwell_time: None,
+            extensions: None,
+        }];
+        let mut new = old.clone();
+        new.channels.remove(0);
+        // only the removal, the references are cleaned up by the patch
+        let removed: Vec<Change> = changes(&old, &new).into_iter().filter(|c| matches!(c, Change::Removed { .. })).collect();
+        assert_eq!(removed.len(), 1);
+        let (patched, conflicts) = apply(&old, removed);
+        assert_eq!(conflicts, Vec::<String>::new());
+        let patched = ids::ids_to_names(&patched);
+        assert_eq!(patched.channels.len(), 1);
+        assert_eq!(patched.zones[0].channels, vec!["Repeater"]);
+        assert_eq!(patched.zones[0].a_channel, None);
+        assert_eq!(patched.scanlists[0].channels, vec!["Repeater"]);
+        assert_eq!(patched.scanlists[0].priority_channel_1, None);
+        assert_eq!(patched.scanlists[0].revert_channel, None);
+    }
+
+    #[test]
+    fn dangling_reference_is_a_conflict() {
+        let mut old = base();
+        old.talkgroups = vec![talkgroup(1, "Local", 2), talkgroup(2, "World", 91)];
+        let mut new = old.clone();
+        new.talkgroups.remove(0);
+        // the target has a channel that still uses the removed talkgroup
+        let mut target = old.clone();
+        target.channels[1].mode = ChannelMode::DMR;
+        target.channels[1].dmr = Some(DmrChannel {
+            timeslot: 2,
+            color_code: 1,
+            talkgroup: Some("tg1".to_string()),
+            talkgroup_list: None,
+            id_name: None,
+            talker_alias_ts1: None,
+            talker_alias_ts2: None,
+            simplex_tdma: None,
+            tdma_adaptive: None,
+            dcdm_leader: None,
+            talkaround: None,
+            reverse: None,
+        });
+        let (patched, conflicts) = patch(&old, &new, &target);
+        assert_eq!(patched.talkgroups.len(), 1);
+        assert_eq!(conflicts, vec!["Patch leaves a dangling reference in Repeater: Channel references missing talkgroup: Local"]);
+    }
+}
@@ -113,6 +113,10 @@ fn pretty_channel(_opt: &Opt, channel: &Channel) -> String {
             },
         )),
     }
+    // mixed channels receive both modes, mode above is the TX mode
+    if channel.fm.is_some() && channel.dmr.is_some() {
+        line.push_str(" mixed");
+    }
     line
 }
 
@@ -206,6 +210,48 @@ fn print_talkgroup_lists(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<d
     Ok(output)
 }
 
+fn print_contacts(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+
+    let mut output = String::new();
+    output.push_str(&format!("\nCONT:{:6} {:8} {:10} {:16} {}\n",
+        "idx", "id", "callsign", "name", "location"));
+    for contact in &codeplug.contacts {
+        output.push_str(&format!("CONT {:6} {:8} {:10} {:16} {}\n",
+            contact.index,
+            contact.id,
+            contact.callsign,
+            contact.name.as_deref().unwrap_or("-"),
+            [&contact.city, &contact.state, &contact.country].iter()
+                .filter_map(|x| x.as_deref())
+                .collect::<Vec<&str>>().join(", "),
+        ));
+    }
+
+    Ok(output)
+}
+
+fn print_receive_memories(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+
+    let mut output = String::new();
+    output.push_str(&format!("\nMEMS:{:4} {:3} {:16} {:12} {}\n",
+        "type", "idx", "name", "freq", "scan"));
+    for (kind, memories) in [("bcst", &codeplug.broadcast_memories), ("air", &codeplug.airband_memories)] {
+        for memory in memories {
+            output.push_str(&format!("MEMS {:4} {:3} {:16} {:12} {}\n",
+                kind,
+                memory.index,
+                memory.name,
+                freq2str(&memory.frequency),
+                if memory.scan { "scan" } else { "-" },
+            ));
+        }
+    }
+
+    Ok(output)
+}
+
 fn print_config(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
 
@@ -221,6 +267,30 @@ fn print_config(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>
                 ));
             }
         }
+        if let Some(settings) = &config.general_settings {
+            output.push_str("  GENERAL\n");
+            output.push_str(&format!("    intro   {:16} {:16}\n",
+                settings.intro_line_1.as_deref().unwrap_or("-"),
+                settings.intro_line_2.as_deref().unwrap_or("-"),
+            ));
+            output.push_str(&format!("    beep={} tot={} mic={} vox={} sq={} psave={} bklight={}\n",
+                settings.beep.map_or("-".to_string(), |b| b.to_string()),
+                pretty_timeout(&settings.tx_tot).trim(),
+                settings.mic_level.map_or("-".to_string(), |l| l.to_string()),
+                settings.vox_level.map_or("-".to_string(), |l| l.to_string()),
+                pretty_squelch(&settings.squelch).trim(),
+                settings.power_save.map_or("-".to_string(), |b| b.to_string()),
+                pretty_timeout(&settings.backlight_timeout).trim(),
+            ));
+            if let Some(raw) = &settings.raw {
+                output.push_str(&format!("    raw: {} settings\n", raw.len()));
+            }
+        }
+        if let Some(offsets) = &config.auto_repeater_offsets {
+            output.push_str(&format!("  OFFSETS {}\n",
+                offsets.iter().map(freq2str).collect::<Vec<String>>().join(", "),
+            ));
+        }
     } else {
         output.push_str(&format!("CFG none"));
     }
@@ -230,6 +300,8 @@ fn print_config(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>
 
 pub fn pretty(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    // display names rather than IDs
+    let codeplug = &crate::ids::ids_to_names(codeplug);
 
     let mut output = String::new();
 
@@ -238,6 +310,8 @@ pub fn pretty(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>>
     output.push_str(print_scanlists(opt, codeplug).unwrap().as_str());
     output.push_str(print_talkgroups(opt, codeplug).unwrap().as_str());
     output.push_str(print_talkgroup_lists(opt, codeplug).unwrap().as_str());
+    output.push_str(print_contacts(opt, codeplug).unwrap().as_str());
+    output.push_str(print_receive_memories(opt, codeplug).unwrap().as_str());
     output.push_str(&format!("\n"));
     output.push_str(print_config(opt, codeplug).unwrap().as_str());
     output.push_str(&format!("\nSRC {}\n", codeplug.source));
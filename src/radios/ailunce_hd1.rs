@@ -7,6 +7,7 @@
 use std::collections::HashMap;
 use rust_decimal::prelude::*;
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::*;
 use crate::structures::*;
@@ -20,6 +21,13 @@ pub fn get_props() -> &'static structures::RadioProperties {
         props.channel_name_width_max = 14;
         props.zones_max = 256;
         props.zone_name_width_max = 16;
+        props.scanlist_name_width_max = 0; // scan lists are not written
+        props.talkgroup_name_width_max = 16;
+        props.talkgroup_list_name_width_max = 16;
+        props.contacts_max = 100_000;
+        props.mixed_mode = false;
+        props.ctcss_custom = false;
+        props.dcs_custom = false;
         // dynamically set
         props.channel_index_width = (props.channels_max as f64).log10().ceil() as usize;
         props.zone_index_width = (props.zones_max as f64).log10().ceil() as usize;
@@ -76,12 +84,14 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - GPS Timing Report TX Contact: [TX Contact,??]
 
 // Contacts.csv
-// - Call Type:
-// - Contacts Alias:
-// - City:
-// - Province:
-// - Country:
-// - Call ID:
+// DMR user directory, talkgroups live in PriorityContacts.csv
+// - Call Type: [Private Call]
+// - Contacts Alias: callsign
+// - City: city
+// - Province: state or province
+// - Country: country
+// - Call ID: DMR ID
+// - (trailing empty column)
 
 type CsvRecord = HashMap<String, String>;
 
@@ -163,12 +173,39 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
             talkgroup: None,
             talkgroup_list: None,
             id_name: None,
+            talker_alias_ts1: None, // not supported
+            talker_alias_ts2: None, // not supported
+            simplex_tdma: None, // not supported
+            tdma_adaptive: None, // not supported
+            dcdm_leader: None, // not supported
+            talkaround: None, // not supported
+            reverse: None, // not supported
         });
     }
 
     Ok(channel)
 }
 
+// Convert the CSV contact hashmap into a DmrContact struct
+fn parse_contact_record(record: &CsvRecord, opt: &Opt) -> Result<DmrContact, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 4, "{:?}", record);
+    static CONTACT_INDEX: AtomicUsize = AtomicUsize::new(1);
+    // empty columns are stored as None
+    let optional = |key: &str| record.get(key).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let contact = DmrContact {
+        index: CONTACT_INDEX.fetch_add(1, Ordering::Relaxed),
+        id: record.get("Call ID").unwrap().trim().parse::<u32>()?,
+        callsign: record.get("Contacts Alias").unwrap().trim().to_string(),
+        name: None, // not supported
+        city: optional("City"),
+        state: optional("Province"),
+        country: optional("Country"),
+        remarks: None, // not supported
+        extensions: None, // all columns are mapped
+    };
+    Ok(contact)
+}
+
 pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 4, "props = {:?}", get_props());
@@ -200,11 +237,65 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
         }
     }
 
+    // check for Contacts.csv
+    let contacts_path: PathBuf = input_path.join("Contacts.csv");
+    // if Contacts.csv doesn't exist, no problem, we just don't have any contacts
+    if contacts_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", contacts_path.display());
+        let mut reader = csv::Reader::from_path(&contacts_path)?;
+        for result in reader.deserialize() {
+            let record: CsvRecord = result?;
+            // HD1 CPS may export rows without an ID, skip them
+            if record.get("Call ID").is_none_or(|id| id.trim().is_empty()) {
+                continue;
+            }
+            // convert from CSV record to DmrContact struct
+            let contact = parse_contact_record(&record, opt)?;
+            // append to codeplug.contacts
+            codeplug.contacts.push(contact);
+        }
+    }
+
     Ok(codeplug)
 }
 
 // WRITE //////////////////////////////////////////////////////////////////////
 
+pub fn write_contacts(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+    .from_path(path)?;
+
+    // write header, HD1 CPS exports a trailing empty column
+    writer.write_record([
+        "Call Type",
+        "Contacts Alias",
+        "City",
+        "Province",
+        "Country",
+        "Call ID",
+        "",
+    ])?;
+
+    for contact in &codeplug.contacts {
+        uprintln!(opt, Stderr, None, 4, "Writing contact {:width$}: {}", contact.id, contact.callsign, width = 8);
+        writer.write_record(&[
+            "Private Call".to_string(), // Call Type
+            contact.callsign.clone(), // Contacts Alias
+            contact.city.clone().unwrap_or_default(), // City
+            contact.state.clone().unwrap_or_default(), // Province
+            contact.country.clone().unwrap_or_default(), // Country
+            contact.id.to_string(), // Call ID
+            "".to_string(),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
 pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
@@ -287,10 +378,23 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
         return Err("Bad output path".into());
     }
 
+    // write to Contacts.csv
+    let contacts_path: PathBuf = output_path.join("Contacts.csv");
+    if !codeplug.contacts.is_empty() {
+        write_contacts(codeplug, &contacts_path, opt)?;
+    }
+
     // write to Channels.csv
     let mut channels_path: PathBuf = output_path.clone();
     channels_path.push("Channels.CSV");
     write_channels(codeplug, &channels_path, opt)?;
 
+    if !codeplug.broadcast_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Broadcast memories are not supported, skipping {} memories", codeplug.broadcast_memories.len());
+    }
+    if !codeplug.airband_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Airband memories are not supported, skipping {} memories", codeplug.airband_memories.len());
+    }
+
     Ok(())
 }
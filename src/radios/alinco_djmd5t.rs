@@ -5,6 +5,7 @@
 use std::path::PathBuf;
 use std::path::Path;
 use std::collections::HashMap;
+use std::collections::BTreeSet;
 use rust_decimal::prelude::*;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -21,6 +22,13 @@ pub fn get_props() -> &'static structures::RadioProperties {
         props.channel_name_width_max = 16;
         props.zones_max = 250;
         props.zone_name_width_max = 16;
+        props.scanlist_name_width_max = 16;
+        props.talkgroup_name_width_max = 16;
+        props.talkgroup_list_name_width_max = 16;
+        props.contacts_max = 200_000;
+        props.mixed_mode = true;
+        props.ctcss_custom = false;
+        props.dcs_custom = true;
         // dynamically set
         props.channel_index_width = (props.channels_max as f64).log10().ceil() as usize;
         props.zone_index_width = (props.zones_max as f64).log10().ceil() as usize;
@@ -28,6 +36,11 @@ pub fn get_props() -> &'static structures::RadioProperties {
     })
 }
 
+// Radio model name, used to key vendor extensions
+fn model() -> &'static str {
+    Path::new(file!()).file_stem().unwrap().to_str().unwrap()
+}
+
 // CSV Export Format
 // Alinco DJ-MD5T CPS Version v1.13e
 /* Files
@@ -51,12 +64,17 @@ pub fn get_props() -> &'static structures::RadioProperties {
  * Zone.CSV
  */
 
+// AutoRepeaterOffsetFrequencies.CSV
+// - No.: offset index
+// - Offset Frequency: offset with units, "600.00 KHz" below 1 MHz, otherwise "5.00000 MHz"
+// - (two unnamed columns): [Off], purpose unknown
+
 // Channel.CSV
 // - No.: Channel index
 // - Channel Name: Channel name
 // - Receive Frequency: frequency in MHz
 // - Transmit Frequency: frequency in MHz
-// - Channel Type: [A-Analog, D-Digital]
+// - Channel Type: [A-Analog, D-Digital, A+D TX A, D+A TX D], mixed types receive both modes and transmit the first
 // - Transmit Power: [Turbo, High, Mid, Low] but manual says "High: 5W, Middle: 2.5W, Low: 1W, Small: 0.2W"
 // - Band Width: [12.5K, 25K]
 // - CTCSS/DCS Decode: Off, or CTCSS/DCS frequency/code
@@ -78,18 +96,35 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Receive Group List: None or RX Group List name
 // - TX Prohibit: [Off, On]
 // - Reverse: [Off, On]
-// - Simplex TDMA: [Off, ??]
-// - TDMA Adaptive: [Off, ??]
+// - Simplex TDMA: [Off, On]
+// - TDMA Adaptive: [Off, On]
 // - Encryption Type: [Normal Encryption, ??]
 // - Digital Encryption: [Off, ??]
 // - Call Confirmation: [Off, ??]
-// - Talk Around: [Off, ??]
+// - Talk Around: [Off, On]
 // - Work Alone: [Off, ??]
 // - Custom CTCSS: [251.1, ??]
 // - 2TONE Decode: [0, ??]
 // - Ranging: [Off, ??]
 // - Through Mode: [Off, ??]
 
+// DigitalContactList.CSV
+// - No.: contact index
+// - Radio ID: DMR ID
+// - Callsign: callsign
+// - Name: name
+// - City: city
+// - State: state or province
+// - Country: country
+// - Remarks: free-form remarks
+// - Call Type: [Private Call]
+// - Call Alert: [None, Ring, Online Alert]
+
+// FM.CSV
+// - No.: broadcast FM memory index
+// - Frequency[MHz]: frequency in MHz
+// - Scan: [Add, Del]
+
 // RadioIDList.CSV
 // - No.: radio ID index
 // - Radio ID: radio ID
@@ -105,11 +140,12 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - No: scan list index
 // - Scan List Name: scan list name
 // - Scan Channel Member: list of channel names, "|" separated
-// - Scan Mode: [Off, ??]
-// - Priority Channel Select: [Off, ??]
-// - Priority Channel 1: [Off, ??]
-// - Priority Channel 2: [Off, ??]
-// - Revert Channel: [Selected, ??]
+// - Scan Mode: [Off, On]
+// - Priority Channel Select: [Off, Priority Channel Select1, Priority Channel Select2, Priority Channel Select1 + Priority Channel Select2]
+// - Priority Channel 1: [Off, Current Channel, channel name]
+// - Priority Channel 2: [Off, Current Channel, channel name]
+// - Revert Channel: [Selected, Selected + TalkBack, Priority Channel Select1, Priority Channel Select2, Last Called, Last Used,
+//   Priority Channel Select1 + TalkBack, Priority Channel Select2 + TalkBack]
 // - Look Back Time A[s]: default 2
 // - Look Back Time B[s]: default 3
 // - Dropout Delay Time[s]: default 3.1
@@ -133,11 +169,15 @@ pub fn get_props() -> &'static structures::RadioProperties {
 
 // READ ///////////////////////////////////////////////////////////////////////
 
+// Columns in TalkGroups.CSV that are mapped into DmrTalkgroup (not passed through extensions)
+const TALKGROUP_MAPPED: [&str; 4] = ["No.", "Radio ID", "Name", "Call Type"];
+
 fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
     static TALKGROUP_INDEX: AtomicUsize = AtomicUsize::new(1);
     let talkgroup = DmrTalkgroup {
         index: TALKGROUP_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         id: record.get("Radio ID").unwrap().parse::<u32>()?,
         name: record.get("Name").unwrap().to_string(),
         call_type: match record.get("Call Type").unwrap().as_str() {
@@ -147,6 +187,8 @@ fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup,
             _ => return Err(format!("Unrecognized call type: {}", record.get("Call Type").unwrap()).into()),
         },
         alert: false, // @TODO FIXME
+        timeslot_override: None, // not supported
+        extensions: super::get_extensions(record, &TALKGROUP_MAPPED, model()),
     };
 
     Ok(talkgroup)
@@ -157,8 +199,10 @@ fn parse_talkgroup_list_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Op
     static TALKGROUP_LIST_INDEX: AtomicUsize = AtomicUsize::new(1);
     let mut talkgroup_list = DmrTalkgroupList {
         index: TALKGROUP_LIST_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         name: record.get("Group Name").unwrap().to_string(),
         talkgroups: Vec::new(),
+        extensions: None, // all columns are mapped
     };
 
     // Talkgroup names are stored as a list, separated by "|"
@@ -175,6 +219,31 @@ fn parse_talkgroup_list_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Op
     Ok(talkgroup_list)
 }
 
+// Columns in DigitalContactList.CSV that are mapped into DmrContact (not passed through extensions)
+const CONTACT_MAPPED: [&str; 8] = [
+    "No.", "Radio ID", "Callsign", "Name", "City", "State", "Country", "Remarks",
+];
+
+// Convert the CSV digital contact hashmap into a DmrContact struct
+fn parse_contact_record(record: &CsvRecord, opt: &Opt) -> Result<DmrContact, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 4, "    {:?}", record);
+    static CONTACT_INDEX: AtomicUsize = AtomicUsize::new(1);
+    // empty columns are stored as None
+    let optional = |key: &str| record.get(key).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let contact = DmrContact {
+        index: CONTACT_INDEX.fetch_add(1, Ordering::Relaxed),
+        id: record.get("Radio ID").unwrap().parse::<u32>()?,
+        callsign: record.get("Callsign").unwrap().trim().to_string(),
+        name: optional("Name"),
+        city: optional("City"),
+        state: optional("State"),
+        country: optional("Country"),
+        remarks: optional("Remarks"),
+        extensions: super::get_extensions(record, &CONTACT_MAPPED, model()),
+    };
+    Ok(contact)
+}
+
 // Convert a string into a TxPermit enum
 fn parse_tx_permit(tx_permit: &str) -> Option<TxPermit> {
     match tx_permit {
@@ -200,6 +269,37 @@ fn parse_tone(tone: &str) -> Option<Tone> {
     return Some(Tone::Ctcss(tone.parse::<f64>().unwrap()));
 }
 
+// Convert an On/Off string into a bool, None if the column is missing
+fn parse_on_off(value: Option<&String>) -> Option<bool> {
+    value.map(|x| x == "On")
+}
+
+// Columns in Channel.CSV that are mapped into Channel (not passed through extensions)
+const CHANNEL_MAPPED: [&str; 12] = [
+    "No.", "Channel Name", "Receive Frequency", "Transmit Frequency", "Channel Type", "Transmit Power",
+    "Contact", "Contact Call Type", "Contact TG/DMR ID", "Radio ID", "Busy Lock/TX Permit", "TX Prohibit",
+];
+// Columns only mapped for channels with FM or DMR settings, the writer fills in defaults otherwise
+const CHANNEL_MAPPED_FM: [&str; 4] = ["Band Width", "CTCSS/DCS Decode", "CTCSS/DCS Encode", "Squelch Mode"];
+const CHANNEL_MAPPED_DMR: [&str; 7] = [
+    "Color Code", "Slot", "Receive Group List", "Reverse", "Simplex TDMA", "TDMA Adaptive", "Talk Around",
+];
+
+// Columns mapped for this channel, Scan List is only mapped when the channel sets one
+fn channel_mapped(channel: &Channel) -> Vec<&'static str> {
+    let mut mapped = CHANNEL_MAPPED.to_vec();
+    if channel.fm.is_some() {
+        mapped.extend(CHANNEL_MAPPED_FM);
+    }
+    if channel.dmr.is_some() {
+        mapped.extend(CHANNEL_MAPPED_DMR);
+    }
+    if let Some(Scan::ScanList(_)) = channel.scan {
+        mapped.push("Scan List");
+    }
+    mapped
+}
+
 // Convert the CSV channel hashmap into a Channel struct
 fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
@@ -207,10 +307,12 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
 
     channel.index = record.get("No.").unwrap().parse::<usize>()?;
     channel.name = record.get("Channel Name").unwrap().to_string();
-    channel.mode = match record.get("Channel Type").unwrap().as_str() {
-        "A-Analog" => ChannelMode::FM,
-        "D-Digital" => ChannelMode::DMR,
-        _ => return Err(format!("Unrecognized channel type: {}", record.get("Channel Type").unwrap()).into()),
+    // mixed channels receive both modes, and transmit in the first
+    let channel_type = record.get("Channel Type").unwrap().as_str();
+    channel.mode = match channel_type {
+        "A-Analog" | "A+D TX A" => ChannelMode::FM,
+        "D-Digital" | "D+A TX D" => ChannelMode::DMR,
+        _ => return Err(format!("Unrecognized channel type: {}", channel_type).into()),
     };
     channel.frequency_rx = Decimal::from_str(record.get("Receive Frequency").unwrap())? * Decimal::new(1_000_000, 0);
     channel.frequency_tx = Decimal::from_str(record.get("Transmit Frequency").unwrap())? * Decimal::new(1_000_000, 0);
@@ -228,7 +330,7 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
         "None" => None,
         _ => Some(Scan::ScanList(record.get("Scan List").unwrap().to_string())),
     };
-    if channel.mode == ChannelMode::FM { // FM specific fields
+    if channel_type != "D-Digital" { // FM specific fields
         channel.fm = Some(FmChannel {
             bandwidth: match record.get("Band Width").unwrap().as_str() {
                 "12.5K" => Decimal::from_str("12.5").unwrap() * Decimal::new(1_000, 0),
@@ -239,7 +341,8 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
             tone_rx: parse_tone(record.get("CTCSS/DCS Decode").unwrap().as_str()),
             tone_tx: parse_tone(record.get("CTCSS/DCS Encode").unwrap().as_str()),
         });
-    } else if channel.mode == ChannelMode::DMR { // DMR specific fields
+    }
+    if channel_type != "A-Analog" { // DMR specific fields
         channel.dmr = Some(DmrChannel {
             timeslot: record.get("Slot").unwrap().parse::<u8>()?,
             color_code: record.get("Color Code").unwrap().parse::<u8>()?,
@@ -252,10 +355,16 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
                 Some(record.get("Receive Group List").unwrap().to_string())
             },
             id_name: Some(record.get("Radio ID").unwrap().to_string()),
+            talker_alias_ts1: None, // not supported
+            talker_alias_ts2: None, // not supported
+            simplex_tdma: parse_on_off(record.get("Simplex TDMA")),
+            tdma_adaptive: parse_on_off(record.get("TDMA Adaptive")),
+            dcdm_leader: None, // not supported
+            talkaround: parse_on_off(record.get("Talk Around")),
+            reverse: parse_on_off(record.get("Reverse")),
         })
-    } else {
-        return Err("Unparsed channel mode".into());
     }
+    channel.extensions = super::get_extensions(record, &channel_mapped(&channel), model());
 
     Ok(channel)
 }
@@ -266,8 +375,13 @@ fn parse_zone_record(csv_zone: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Re
     static ZONE_INDEX: AtomicUsize = AtomicUsize::new(1);
     let mut zone = Zone {
         index: ZONE_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         name: csv_zone.get("Zone Name").unwrap().to_string(),
         channels: Vec::new(),
+        a_channel: csv_zone.get("A Channel").filter(|x| !x.is_empty()).cloned(),
+        b_channel: csv_zone.get("B Channel").filter(|x| !x.is_empty()).cloned(),
+        hidden: None, // not supported
+        extensions: None, // all columns are mapped
     };
 
     // Channels are stored as a list of names, separated by "|"
@@ -284,6 +398,30 @@ fn parse_zone_record(csv_zone: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Re
     Ok(zone)
 }
 
+// Convert a priority channel string into a ScanListChannel struct
+fn parse_scan_list_channel(channel: &str) -> Option<ScanListChannel> {
+    match channel {
+        "Off" | "" => None,
+        "Current Channel" => Some(ScanListChannel::Selected),
+        _ => Some(ScanListChannel::Channel(channel.to_string())),
+    }
+}
+
+// Convert a revert channel string into a ScanRevert struct
+fn parse_scan_revert(revert: &str) -> Option<ScanRevert> {
+    match revert {
+        "Selected" => Some(ScanRevert::Selected),
+        "Selected + TalkBack" => Some(ScanRevert::SelectedTalkback),
+        "Priority Channel Select1" => Some(ScanRevert::Priority1),
+        "Priority Channel Select2" => Some(ScanRevert::Priority2),
+        "Priority Channel Select1 + TalkBack" => Some(ScanRevert::Priority1Talkback),
+        "Priority Channel Select2 + TalkBack" => Some(ScanRevert::Priority2Talkback),
+        "Last Called" => Some(ScanRevert::LastCalled),
+        "Last Used" => Some(ScanRevert::LastUsed),
+        _ => None,
+    }
+}
+
 // Convert the CSV scanlist hashmap into a ScanList struct
 fn parse_scanlist_record(csv_scanlist: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Result<ScanList, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", csv_scanlist);
@@ -292,8 +430,24 @@ fn parse_scanlist_record(csv_scanlist: &CsvRecord, codeplug: &Codeplug, opt: &Op
         index: SCANLIST_INDEX.fetch_add(1, Ordering::Relaxed),
         name: csv_scanlist.get("Scan List Name").unwrap().to_string(),
         channels: Vec::new(),
+        scan_mode: Some(csv_scanlist.get("Scan Mode").unwrap() != "Off"),
+        revert_channel: parse_scan_revert(csv_scanlist.get("Revert Channel").unwrap()),
+        look_back_time_a: csv_scanlist.get("Look Back Time A[s]").unwrap().parse::<f64>().ok(),
+        look_back_time_b: csv_scanlist.get("Look Back Time B[s]").unwrap().parse::<f64>().ok(),
+        dropout_delay_time: csv_scanlist.get("Dropout Delay Time[s]").unwrap().parse::<f64>().ok(),
+        dwell_time: csv_scanlist.get("Dwell Time[s]").unwrap().parse::<f64>().ok(),
+        ..Default::default()
     };
 
+    // Priority channels are only set if enabled by Priority Channel Select
+    let priority_select = csv_scanlist.get("Priority Channel Select").unwrap();
+    if priority_select.contains("Select1") {
+        scanlist.priority_channel_1 = parse_scan_list_channel(csv_scanlist.get("Priority Channel 1").unwrap());
+    }
+    if priority_select.contains("Select2") {
+        scanlist.priority_channel_2 = parse_scan_list_channel(csv_scanlist.get("Priority Channel 2").unwrap());
+    }
+
     // Channels are stored as a list of names, separated by "|"
     let channel_names: Vec<&str> = csv_scanlist.get("Scan Channel Member").unwrap().split('|').collect();
     for name in channel_names {
@@ -308,6 +462,30 @@ fn parse_scanlist_record(csv_scanlist: &CsvRecord, codeplug: &Codeplug, opt: &Op
     Ok(scanlist)
 }
 
+// Convert the CSV FM hashmap into a ReceiveMemory struct
+fn parse_broadcast_memory_record(record: &CsvRecord, opt: &Opt) -> Result<ReceiveMemory, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 4, "    {:?}", record);
+    let memory = ReceiveMemory {
+        index: record.get("No.").unwrap().parse::<usize>()?,
+        name: "".to_string(), // FM memories are not named
+        frequency: Decimal::from_str(record.get("Frequency[MHz]").unwrap())? * Decimal::new(1_000_000, 0),
+        scan: record.get("Scan").unwrap() == "Add",
+    };
+
+    Ok(memory)
+}
+
+// Convert an offset frequency string ("600.00 KHz" or "5.00000 MHz") into Hz
+fn parse_offset_frequency(offset: &str) -> Result<Decimal, Box<dyn Error>> {
+    let (value, unit) = offset.trim().split_once(' ').ok_or(format!("Unrecognized offset frequency: {}", offset))?;
+    let value = Decimal::from_str(value)?;
+    match unit {
+        "KHz" => Ok(value * Decimal::new(1_000, 0)),
+        "MHz" => Ok(value * Decimal::new(1_000_000, 0)),
+        _ => Err(format!("Unrecognized offset frequency: {}", offset).into()),
+    }
+}
+
 // Convert the CSV DMR ID hashmap into a DMRId struct
 fn parse_dmr_id_record(csv_dmr_id: &CsvRecord, opt: &Opt) -> Result<DmrId, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", csv_dmr_id);
@@ -365,6 +543,22 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
         }
     }
 
+    // Check for DigitalContactList.CSV
+    let mut contacts_path: PathBuf = input_path.clone();
+    contacts_path.push("DigitalContactList.CSV");
+    // if this file doesn't exist, no problem, we just don't have any contacts
+    if contacts_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", contacts_path.display());
+        let mut reader = csv::Reader::from_path(contacts_path)?;
+        for result in reader.deserialize() {
+            let record: CsvRecord = result?;
+            // convert from CSV record to DmrContact struct
+            let contact = parse_contact_record(&record, opt)?;
+            // append to codeplug.contacts
+            codeplug.contacts.push(contact);
+        }
+    }
+
     // Check for Channel.CSV
     let mut channels_path: PathBuf = input_path.clone();
     channels_path.push("Channel.CSV");
@@ -419,6 +613,21 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
         }
     }
 
+    // Check for FM.CSV
+    let mut broadcast_path: PathBuf = input_path.clone();
+    broadcast_path.push("FM.CSV");
+    // if FM.CSV doesn't exist, no problem, we just don't have any broadcast memories
+    if broadcast_path.exists() {
+        let mut reader = csv::Reader::from_path(broadcast_path)?;
+        for result in reader.deserialize() {
+            let record: CsvRecord = result?;
+            // convert from CSV record to ReceiveMemory struct
+            let memory = parse_broadcast_memory_record(&record, opt)?;
+            // append to codeplug.broadcast_memories
+            codeplug.broadcast_memories.push(memory);
+        }
+    }
+
     // Check for RadioIDList.CSV
     let mut radio_id_list_path: PathBuf = input_path.clone();
     radio_id_list_path.push("RadioIDList.CSV");
@@ -435,12 +644,39 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
                     dmr_configuration: Some(DmrConfiguration {
                         id_list: Vec::new(),
                     }),
+                    general_settings: None,
+                    auto_repeater_offsets: None,
                 });
             }
             codeplug.config.as_mut().unwrap().dmr_configuration.as_mut().unwrap().id_list.push(dmr_id);
         }
     }
 
+    // Check for AutoRepeaterOffsetFrequencies.CSV
+    let mut offsets_path: PathBuf = input_path.clone();
+    offsets_path.push("AutoRepeaterOffsetFrequencies.CSV");
+    // if this file doesn't exist, no problem, the offsets will come from the bandplan
+    if offsets_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", offsets_path.display());
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_path(offsets_path)?;
+        let mut offsets: Vec<Decimal> = Vec::new();
+        for result in reader.records() {
+            let record = result?;
+            // the second column is the offset, ignore any unnamed trailing columns
+            offsets.push(parse_offset_frequency(record.get(1).unwrap_or(""))?);
+        }
+        if codeplug.config.is_none() {
+            codeplug.config = Some(Configuration {
+                dmr_configuration: None,
+                general_settings: None,
+                auto_repeater_offsets: None,
+            });
+        }
+        codeplug.config.as_mut().unwrap().auto_repeater_offsets = Some(offsets);
+    }
+
     Ok(codeplug)
 }
 
@@ -456,17 +692,19 @@ pub fn write_talkgroups(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Resul
         .from_path(path)?;
 
     // write the header
-    writer.write_record(&[
+    let header = [
         "No.",
         "Radio ID",
         "Name",
         "Call Type",
         "Call Alert",
-    ])?;
+    ];
+    writer.write_record(header)?;
 
+    let mut dropped = BTreeSet::new();
     for (ii, talkgroup) in codeplug.talkgroups.iter().enumerate() {
         uprintln!(opt, Stderr, None, 4, "Writing talkgroup {:width$}: {:?}", talkgroup.id, talkgroup.name, width = 4);
-        writer.write_record(&[
+        let mut record = [
             format!("{}", ii + 1), // No.
             talkgroup.id.to_string(), // Radio ID
             talkgroup.name.clone(), // Name
@@ -476,10 +714,13 @@ pub fn write_talkgroups(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Resul
                 DmrTalkgroupCallType::AllCall => "All Call".to_string(),
             }, // Call Type
             "None".to_string(), // Call Alert
-        ])?;
+        ];
+        super::apply_extensions(&header, &mut record, &TALKGROUP_MAPPED, &talkgroup.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
     }
 
     writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
 
     Ok(())
 }
@@ -534,6 +775,55 @@ pub fn write_talkgroup_lists(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) ->
     Ok(())
 }
 
+pub fn write_contacts(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Alinco CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    let header = [
+        "No.",
+        "Radio ID",
+        "Callsign",
+        "Name",
+        "City",
+        "State",
+        "Country",
+        "Remarks",
+        "Call Type",
+        "Call Alert",
+    ];
+    writer.write_record(header)?;
+
+    let mut dropped = BTreeSet::new();
+    for (ii, contact) in codeplug.contacts.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing contact {:width$}: {}", contact.id, contact.callsign, width = 8);
+        let mut record = [
+            format!("{}", ii + 1), // No.
+            contact.id.to_string(), // Radio ID
+            contact.callsign.clone(), // Callsign
+            contact.name.clone().unwrap_or_default(), // Name
+            contact.city.clone().unwrap_or_default(), // City
+            contact.state.clone().unwrap_or_default(), // State
+            contact.country.clone().unwrap_or_default(), // Country
+            contact.remarks.clone().unwrap_or_default(), // Remarks
+            "Private Call".to_string(), // Call Type
+            "None".to_string(), // Call Alert
+        ];
+        super::apply_extensions(&header, &mut record, &CONTACT_MAPPED, &contact.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
+    }
+
+    writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
+
+    Ok(())
+}
+
 fn write_power(power: &Power) -> String {
     match power {
         Power::Default => "High".to_string(),
@@ -602,6 +892,29 @@ fn write_radio_id(channel: &Channel, codeplug: &Codeplug) -> String {
     "".to_string()
 }
 
+fn write_on_off(value: Option<bool>) -> String {
+    if value == Some(true) { "On" } else { "Off" }.to_string()
+}
+
+fn write_tone(tone: &Option<Tone>) -> String {
+    match tone {
+        Some(Tone::Ctcss(ctcss)) => format!("{:0.1}", ctcss),
+        Some(Tone::Dcs(dcs)) => dcs.clone(),
+        None => "Off".to_string(),
+    }
+}
+
+// Mixed channels have both fm and dmr set, with channel.mode as the TX mode
+fn write_channel_type(channel: &Channel) -> String {
+    let mixed = channel.fm.is_some() && channel.dmr.is_some();
+    match channel.mode {
+        ChannelMode::DMR if mixed => "D+A TX D",
+        ChannelMode::DMR => "D-Digital",
+        _ if mixed => "A+D TX A",
+        _ => "A-Analog",
+    }.to_string()
+}
+
 fn write_tx_permit(channel: &Channel) -> String {
     let tx_permit = match &channel.tx_permit {
         Some(TxPermit::Always) => "Always".to_string(),
@@ -613,9 +926,12 @@ fn write_tx_permit(channel: &Channel) -> String {
 }
 
 // scan list needs to be set in the channel
-// right now, we build the scan list from the zone
+// use the channel's scan list if it has one, otherwise build the scan list from the zone
 // so just pick the first zone that contains the channel, and set that as the scan list (if it exists)
 fn write_scan_list(channel: &Channel, codeplug: &Codeplug) -> String {
+    if let Some(Scan::ScanList(name)) = &channel.scan {
+        return name.clone();
+    }
     for zone in &codeplug.zones {
         if zone.channels.contains(&channel.name) {
             return zone.name.clone();
@@ -634,7 +950,7 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
         .from_path(path)?;
 
     // write the header
-    writer.write_record(&[
+    let header = [
         "No.",
         "Channel Name",
         "Receive Frequency",
@@ -672,42 +988,30 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
         "2TONE Decode",
         "Ranging",
         "Through Mode",
-    ])?;
+    ];
+    writer.write_record(header)?;
 
+    let mut dropped = BTreeSet::new();
     for channel in &codeplug.channels {
         uprintln!(opt, Stderr, None, 4, "Writing channel {:width$}: {}", channel.index, channel.name, width = get_props().channel_index_width);
         uprintln!(opt, Stderr, None, 4, "    {:?}", channel);
 
         let contact = get_contact_tuple(&channel, &codeplug);
-        if channel.mode == ChannelMode::FM {
-            writer.write_record(&[
+        let mut record = if channel.mode == ChannelMode::FM {
+            [
                 channel.index.to_string(), // No.
                 channel.name.clone(), // Channel Name
                 format!("{:0.5}", (channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Receive Frequency
                 format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Transmit Frequency
-                "A-Analog".to_string(), // Channel Type
+                write_channel_type(channel), // Channel Type
                 write_power(&channel.power), // Transmit Power
                 match channel.fm.clone().unwrap().bandwidth.to_f64().unwrap() {
                     12_500.0 => "12.5K".to_string(),
                     25_000.0 => "25K".to_string(),
                     _ => return Err("Unrecognized bandwidth".into()),
                 }, // Band Width
-                if let Some(tone) = &channel.fm.as_ref().unwrap().tone_rx {
-                    match tone {
-                        Tone::Ctcss(ctcss) => format!("{:0.1}", ctcss),
-                        Tone::Dcs(dcs) => dcs.clone(),
-                    }
-                } else {
-                    "Off".to_string()
-                }, // CTCSS/DCS Decode
-                if let Some(tone) = &channel.fm.as_ref().unwrap().tone_tx {
-                    match tone {
-                        Tone::Ctcss(ctcss) => format!("{:0.1}", ctcss),
-                        Tone::Dcs(dcs) => dcs.clone(),
-                    }
-                } else {
-                    "Off".to_string()
-                }, // CTCSS/DCS Encode
+                write_tone(&channel.fm.as_ref().unwrap().tone_rx), // CTCSS/DCS Decode
+                write_tone(&channel.fm.as_ref().unwrap().tone_tx), // CTCSS/DCS Encode
                 contact.0, // Contact
                 contact.1, // Contact Call Type
                 contact.2, // Contact TG/DMR ID
@@ -723,45 +1027,57 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 "1".to_string(), // 2Tone ID
                 "1".to_string(), // 5Tone ID
                 "Off".to_string(), // PTT ID
-                "1".to_string(), // Color Code
-                "1".to_string(), // Slot
+                channel.dmr.as_ref().map_or("1".to_string(), |dmr| dmr.color_code.to_string()), // Color Code
+                channel.dmr.as_ref().map_or("1".to_string(), |dmr| dmr.timeslot.to_string()), // Slot
                 write_scan_list(&channel, &codeplug), // Scan List
-                "None".to_string(), // Receive Group List
+                if let Some(talkgroup_list) = channel.dmr.as_ref().and_then(|dmr| dmr.talkgroup_list.as_ref()) {
+                    talkgroup_list.clone()
+                } else {
+                    "None".to_string()
+                }, // Receive Group List
                 if channel.rx_only {
                     "On".to_string()
                 } else {
                     "Off".to_string()
                 }, // TX Prohibit
-                "Off".to_string(), // Reverse
-                "Off".to_string(), // Simplex TDMA
-                "Off".to_string(), // TDMA Adaptive
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.reverse)), // Reverse
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.simplex_tdma)), // Simplex TDMA
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.tdma_adaptive)), // TDMA Adaptive
                 "Normal Encryption".to_string(), // Encryption Type
                 "Off".to_string(), // Digital Encryption
                 "Off".to_string(), // Call Confirmation
-                "Off".to_string(), // Talk Around
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.talkaround)), // Talk Around
                 "Off".to_string(), // Work Alone
                 "251.1".to_string(), // Custom CTCSS
                 "0".to_string(), // 2TONE Decode
                 "Off".to_string(), // Ranging
                 "Off".to_string(), // Through Mode
-            ])?;
+            ]
         } else if channel.mode == ChannelMode::DMR {
-            writer.write_record(&[
+            [
                 channel.index.to_string(), // No.
                 channel.name.clone(), // Channel Name
                 format!("{:0.5}", (channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Receive Frequency
                 format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Transmit Frequency
-                "D-Digital".to_string(), // Channel Type
+                write_channel_type(channel), // Channel Type
                 write_power(&channel.power), // Transmit Power
-                "12.5K".to_string(), // Band Width
-                "Off".to_string(), // CTCSS/DCS Decode
-                "Off".to_string(), // CTCSS/DCS Encode
+                match channel.fm.as_ref().map_or(12_500.0, |fm| fm.bandwidth.to_f64().unwrap()) {
+                    12_500.0 => "12.5K".to_string(),
+                    25_000.0 => "25K".to_string(),
+                    _ => return Err("Unrecognized bandwidth".into()),
+                }, // Band Width
+                channel.fm.as_ref().map_or("Off".to_string(), |fm| write_tone(&fm.tone_rx)), // CTCSS/DCS Decode
+                channel.fm.as_ref().map_or("Off".to_string(), |fm| write_tone(&fm.tone_tx)), // CTCSS/DCS Encode
                 contact.0, // Contact
                 contact.1, // Contact Call Type
                 contact.2, // Contact TG/DMR ID
                 write_radio_id(&channel, &codeplug), // Radio ID
                 write_tx_permit(&channel), // Busy Lock/TX Permit
-                "Carrier".to_string(), // Squelch Mode
+                if channel.fm.as_ref().is_some_and(|fm| fm.tone_rx.is_some()) {
+                    "CTCSS/DCS".to_string()
+                } else {
+                    "Carrier".to_string()
+                }, // Squelch Mode
                 "Off".to_string(), // Optional Signal
                 "1".to_string(), // DTMF ID
                 "1".to_string(), // 2Tone ID
@@ -780,29 +1096,45 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 } else {
                     "Off".to_string()
                 }, // TX Prohibit
-                "Off".to_string(), // Reverse
-                "Off".to_string(), // Simplex TDMA
-                "Off".to_string(), // TDMA Adaptive
+                write_on_off(channel.dmr.as_ref().unwrap().reverse), // Reverse
+                write_on_off(channel.dmr.as_ref().unwrap().simplex_tdma), // Simplex TDMA
+                write_on_off(channel.dmr.as_ref().unwrap().tdma_adaptive), // TDMA Adaptive
                 "Normal Encryption".to_string(), // Encryption Type
                 "Off".to_string(), // Digital Encryption
                 "Off".to_string(), // Call Confirmation
-                "Off".to_string(), // Talk Around
+                write_on_off(channel.dmr.as_ref().unwrap().talkaround), // Talk Around
                 "Off".to_string(), // Work Alone
                 "251.1".to_string(), // Custom CTCSS
                 "0".to_string(), // 2TONE Decode
                 "Off".to_string(), // Ranging
                 "Off".to_string(), // Through Mode
-            ])?;
+            ]
         } else {
             uprintln!(opt, Stderr, Color::Red, None, "Unsupported channel mode: index = {}, mode = {:?}", channel.index, channel.mode);
-        }
+            continue;
+        };
+        super::apply_extensions(&header, &mut record, &channel_mapped(channel), &channel.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
     }
 
     writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
 
     Ok(())
 }
 
+// Get the A and B channel names for a zone, falling back to the first and second channels in the zone
+// if unset or not in the codeplug (they should be members of the zone, validate_generic() warns if not)
+fn write_zone_ab_channels<'a>(zone: &'a Zone, codeplug: &Codeplug) -> (&'a String, &'a String) {
+    let a_channel = zone.a_channel.as_ref()
+        .filter(|x| codeplug.channels.iter().any(|c| c.name == **x))
+        .unwrap_or(&zone.channels[0]);
+    let b_channel = zone.b_channel.as_ref()
+        .filter(|x| codeplug.channels.iter().any(|c| c.name == **x))
+        .unwrap_or(zone.channels.get(1).unwrap_or(&zone.channels[0]));
+    (a_channel, b_channel)
+}
+
 pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
@@ -832,10 +1164,10 @@ pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
             let channel = codeplug.channels.iter().find(|&c| c.name == *name).unwrap();
             channel_names.push_str(&channel.name);
         }
-        // get the first channel in the zone
-        let a_channel = codeplug.channels.iter().find(|&c| c.name == zone.channels[0]).unwrap();
-        // get the second channel in the zone, if it exists
-        let b_channel = codeplug.channels.iter().find(|&c| c.name == *zone.channels.get(1).unwrap_or(&zone.channels[0])).unwrap();
+        // use the selected A/B channels if set, otherwise the first and second channels in the zone
+        let (a_name, b_name) = write_zone_ab_channels(zone, codeplug);
+        let a_channel = codeplug.channels.iter().find(|&c| c.name == *a_name).unwrap();
+        let b_channel = codeplug.channels.iter().find(|&c| c.name == *b_name).unwrap();
         writer.write_record(&[
             format!("{}", ii + 1), // No.
             zone.name.clone(), // Zone Name
@@ -849,6 +1181,40 @@ pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
     Ok(())
 }
 
+fn write_scan_list_channel(channel: &Option<ScanListChannel>) -> String {
+    match channel {
+        None => "Off".to_string(),
+        Some(ScanListChannel::Selected) => "Current Channel".to_string(),
+        Some(ScanListChannel::Channel(name)) => name.clone(),
+    }
+}
+
+fn write_priority_channel_select(scanlist: &ScanList) -> String {
+    match (&scanlist.priority_channel_1, &scanlist.priority_channel_2) {
+        (None, None) => "Off".to_string(),
+        (Some(_), None) => "Priority Channel Select1".to_string(),
+        (None, Some(_)) => "Priority Channel Select2".to_string(),
+        (Some(_), Some(_)) => "Priority Channel Select1 + Priority Channel Select2".to_string(),
+    }
+}
+
+fn write_scan_revert(scanlist: &ScanList, opt: &Opt) -> String {
+    match &scanlist.revert_channel {
+        None | Some(ScanRevert::Selected) => "Selected",
+        Some(ScanRevert::SelectedTalkback) => "Selected + TalkBack",
+        Some(ScanRevert::Priority1) => "Priority Channel Select1",
+        Some(ScanRevert::Priority2) => "Priority Channel Select2",
+        Some(ScanRevert::Priority1Talkback) => "Priority Channel Select1 + TalkBack",
+        Some(ScanRevert::Priority2Talkback) => "Priority Channel Select2 + TalkBack",
+        Some(ScanRevert::LastCalled) => "Last Called",
+        Some(ScanRevert::LastUsed) => "Last Used",
+        Some(ScanRevert::Channel(name)) => {
+            uprintln!(opt, Stderr, Color::Yellow, None, "Scan list {}: revert to a fixed channel ({}) not supported, using Selected", scanlist.name, name);
+            "Selected"
+        },
+    }.to_string()
+}
+
 pub fn write_scanlists(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
@@ -889,15 +1255,15 @@ pub fn write_scanlists(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result
             format!("{}", ii + 1), // No.
             scanlist.name.clone(), // Scan List Name
             channel_names, // Scan Channel Member
-            "Off".to_string(), // Scan Mode
-            "Off".to_string(), // Priority Channel Select
-            "Off".to_string(), // Priority Channel 1
-            "Off".to_string(), // Priority Channel 2
-            "Selected".to_string(), // Revert Channel
-            "2.0".to_string(), // Look Back Time A[s]
-            "3.0".to_string(), // Look Back Time B[s]
-            "3.1".to_string(), // Dropout Delay Time[s]
-            "3.1".to_string(), // Dwell Time[s]
+            if scanlist.scan_mode == Some(true) { "On" } else { "Off" }.to_string(), // Scan Mode
+            write_priority_channel_select(scanlist), // Priority Channel Select
+            write_scan_list_channel(&scanlist.priority_channel_1), // Priority Channel 1
+            write_scan_list_channel(&scanlist.priority_channel_2), // Priority Channel 2
+            write_scan_revert(scanlist, opt), // Revert Channel
+            format!("{:0.1}", scanlist.look_back_time_a.unwrap_or(2.0)), // Look Back Time A[s]
+            format!("{:0.1}", scanlist.look_back_time_b.unwrap_or(3.0)), // Look Back Time B[s]
+            format!("{:0.1}", scanlist.dropout_delay_time.unwrap_or(3.1)), // Dropout Delay Time[s]
+            format!("{:0.1}", scanlist.dwell_time.unwrap_or(3.1)), // Dwell Time[s]
         ])?;
     }
 
@@ -934,6 +1300,74 @@ pub fn write_radio_id_list(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Re
     Ok(())
 }
 
+pub fn write_broadcast_memories(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Alinco CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    writer.write_record([
+        "No.",
+        "Frequency[MHz]",
+        "Scan",
+    ])?;
+
+    for (ii, memory) in codeplug.broadcast_memories.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing broadcast memory {:width$}: {}", ii + 1, freq2str(&memory.frequency), width = 3);
+        writer.write_record(&[
+            format!("{}", ii + 1), // No.
+            format!("{:0.3}", (memory.frequency / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Frequency[MHz]
+            if memory.scan { "Add" } else { "Del" }.to_string(), // Scan
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_offset_frequency(offset: &Decimal) -> String {
+    if *offset < Decimal::new(1_000_000, 0) {
+        format!("{:0.2} KHz", (offset / Decimal::new(1_000, 0)).to_f64().unwrap())
+    } else {
+        format!("{:0.5} MHz", (offset / Decimal::new(1_000_000, 0)).to_f64().unwrap())
+    }
+}
+
+pub fn write_auto_repeater_offsets(offsets: &[Decimal], path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Alinco CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    writer.write_record([
+        "No.",
+        "Offset Frequency",
+        "",
+        "",
+    ])?;
+
+    for (ii, offset) in offsets.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing offset {:width$}: {}", ii + 1, freq2str(offset), width = 3);
+        writer.write_record(&[
+            format!("{}", ii + 1), // No.
+            write_offset_frequency(offset), // Offset Frequency
+            "Off".to_string(),
+            "Off".to_string(),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
 pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 4, "props = {:?}", get_props());
@@ -972,6 +1406,13 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
         write_talkgroup_lists(&codeplug, &talkgroup_lists_path, &opt)?;
     }
 
+    // write DigitalContactList.CSV
+    let mut contacts_path: PathBuf = output_path.clone();
+    contacts_path.push("DigitalContactList.CSV");
+    if !codeplug.contacts.is_empty() {
+        write_contacts(codeplug, &contacts_path, opt)?;
+    }
+
     // write Channel.CSV
     let mut channels_path: PathBuf = output_path.clone();
     channels_path.push("Channel.CSV");
@@ -1002,6 +1443,30 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
         }
     }
 
+    // write to AutoRepeaterOffsetFrequencies.CSV
+    // use the offsets from the source codeplug if present, otherwise fill from the bandplan
+    // the Alinco CPS doesn't export optional settings, so the VHF/UHF auto-repeater offsets can't be selected here
+    let mut offsets_path: PathBuf = output_path.clone();
+    offsets_path.push("AutoRepeaterOffsetFrequencies.CSV");
+    let offsets = match codeplug.config.as_ref().and_then(|config| config.auto_repeater_offsets.clone()) {
+        Some(offsets) => offsets,
+        None => {
+            uprintln!(opt, Stderr, Color::Yellow, None, "Auto-repeater offsets filled from the bandplan, but auto-offset is not configured (select the VHF/UHF offsets in the CPS)");
+            bandplan::get_repeater_offsets(&bandplan::load_bandplan(opt)?)
+        }
+    };
+    write_auto_repeater_offsets(&offsets, &offsets_path, opt)?;
+
+    // write to FM.CSV
+    let mut broadcast_path: PathBuf = output_path.clone();
+    broadcast_path.push("FM.CSV");
+    if !codeplug.broadcast_memories.is_empty() {
+        write_broadcast_memories(codeplug, &broadcast_path, opt)?;
+    }
+    if !codeplug.airband_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Airband memories are not supported, skipping {} memories", codeplug.airband_memories.len());
+    }
+
     Ok(())
 }
 
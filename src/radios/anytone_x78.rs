@@ -6,6 +6,8 @@
 use std::path::PathBuf;
 use std::path::Path;
 use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use rust_decimal::prelude::*;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -22,6 +24,13 @@ pub fn get_props() -> &'static structures::RadioProperties {
         props.channel_name_width_max = 16;
         props.zones_max = 250;
         props.zone_name_width_max = 16;
+        props.scanlist_name_width_max = 16;
+        props.talkgroup_name_width_max = 16;
+        props.talkgroup_list_name_width_max = 16;
+        props.contacts_max = 500_000;
+        props.mixed_mode = true;
+        props.ctcss_custom = true;
+        props.dcs_custom = true;
         // dynamically set
         props.channel_index_width = (props.channels_max as f64).log10().ceil() as usize;
         props.zone_index_width = (props.zones_max as f64).log10().ceil() as usize;
@@ -29,6 +38,11 @@ pub fn get_props() -> &'static structures::RadioProperties {
     })
 }
 
+// Radio model name, used to key vendor extensions
+fn model() -> &'static str {
+    Path::new(file!()).file_stem().unwrap().to_str().unwrap()
+}
+
 // CSV Export Format
 // Anytone D878UV CPS Version 3.04
 /* Files
@@ -59,12 +73,16 @@ pub fn get_props() -> &'static structures::RadioProperties {
  * Zone.CSV
  */
 
+// AutoRepeaterOffsetFrequencys.CSV
+// - No.: offset index
+// - Offset Frequency: offset with units, "600.00 KHz" below 1 MHz, otherwise "5.00000 MHz"
+
 // Channel.CSV
 // - No.: channel Index
 // - Channel Name: 16 characters?
 // - Receive Frequency: frequency in MHz
 // - Transmit Frequency: frequency in MHz
-// - Channel Type: [A-Analog, D-Digital]
+// - Channel Type: [A-Analog, D-Digital, A+D TX A, D+A TX D], mixed types receive both modes and transmit the first
 // - Transmit Power: [Turbo, High, Mid, Low], corresponding to ~7W, 5W, 2.5W, 1W
 // - Band Width: [12.5K, 25K]
 // - CTCSS/DCS Decode: Off, or CTCSS/DCS frequency/code
@@ -86,17 +104,17 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Receive Group List: None or RX Group List name
 // - PTT Prohibit: [Off, On]
 // - Reverse: [Off, On]
-// - Simplex TDMA: [Off, ??]
-// - Slot Suit: [Off, ??]
+// - Simplex TDMA: [Off, On], the D578 CPS calls this TDMA
+// - Slot Suit: [Off, On], adaptive TDMA timeslot, older CPS versions call this TDMA Adaptive
 // - AES Digital Encryption: Normal Encryption
 // - Digital Encryption: [Off, ???]
 // - Call Confirmation: [Off, ???]
-// - Talk Around(Simplex): [Off, ???]
+// - Talk Around(Simplex): [Off, On]
 // - Work Alone: [Off, ???]
 // - Custom CTCSS: 251.1 or custom frequency
 // - 2TONE Decode: 0
 // - Ranging: [Off, ???]
-// - Through Mode: [Off, ???]
+// - Through Mode: [Off, ???], the D578 CPS calls this Simplex
 // - APRS RX: [Off, ???]
 // - Analog APRS PTT Mode: [Off, ???]
 // - Digital APRS PTT Mode: [Off, ???]
@@ -104,18 +122,56 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Digital APRS Report Channel: 1
 // - Correct Frequency[Hz]: 0
 // - SMS Confirmation: [Off, ???]
-// - Exclude channel from roaming: [0, 1]
+// - Exclude channel from roaming: [0, 1], the D578 CPS calls this Exclude Channel From Roaming
 // - DMR MODE: 0
 // - DataACK Disable: 0
 // - R5toneBot: 0
 // - R5ToneEot: 0
 // - Auto Scan: 0
 // - Ana Aprs Mute: 0
-// - Send Talker Alias: 0
+// - Send Talker Alias: [0, 1], sent on the channel's timeslot
 // - AnaAprsTxPath: 0
 // - ARC4: 0
 // - ex_emg_kind: 0
 
+// AMAir.CSV (D578 only)
+// - No.: airband memory index
+// - Frequency[MHz]: frequency in MHz
+// - Scan: [0, 1]
+// - Name: memory name, padded to 16 characters (empty names are a NUL followed by spaces)
+
+// DigitalContactList.CSV
+// - No.: contact index
+// - Radio ID: DMR ID
+// - Callsign: callsign
+// - Name: name
+// - City: city
+// - State: state or province
+// - Country: country
+// - Remarks: free-form remarks
+// - Call Type: [Private Call]
+// - Call Alert: [None, Ring, Online Alert]
+
+// FM.CSV
+// - No.: broadcast FM memory index
+// - Frequency[MHz]: frequency in MHz
+// - Scan: [Add, Del]
+
+// OptionalSetting.CSV
+// A single row of ~170 radio-wide settings, mostly stored as indices into the CPS dropdowns
+// - Beep: key beep, [0 = Off, 1 = On]
+// - TOT: transmit timeout, 0 is infinite, otherwise multiples of 30 seconds
+// - SQL1, SQL2: squelch level for VFO A/B, [0 = Off, 1..5]
+// - PowerSave: [0 = Off, 1 = 1:1, 2 = 1:2]
+// - VOX: [0 = Off, 1..3]
+// - MicLevel: [0..4], corresponding to mic gain 1..5
+// - AutoBKLightTime: backlight timeout, [0 = Always, 1 = 5s, ... 15 = 60min], see BACKLIGHT_TIMEOUTS
+// - Start_Char: startup text, first line
+// - Start_Char2: startup text, second line
+// - VhfAutoRep, UhfAutoRep: VFO A auto-repeater offset, [0 = Off, 1-based index into AutoRepeaterOffsetFrequencys.CSV]
+// - VhfAutoRep2, UhfAutoRep2: same, for VFO B
+// - everything else is passed through as-is
+
 // RadioIDList.CSV
 // - No.: radio ID index
 // - Radio ID: radio ID
@@ -133,15 +189,16 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Scan Channel Member: list of channel names, "|" separated
 // - Scan Channel Member RX Frequency: list of channel RX frequencies in MHz, "|" separated
 // - Scan Channel Member TX Frequency: list of channel TX frequencies in MHz, "|" separated
-// - Scan Mode: [Off, ???]
-// - Priority Channel Select: [Off, ???]
-// - Priority Channel 1: [Off, ???]
-// - Priority Channel 1 RX Frequency: [blank, ???]
-// - Priority Channel 1 TX Frequency: [blank, ???]
-// - Priority Channel 2: [Off, ???]
-// - Priority Channel 2 RX Frequency: [blank, ???]
-// - Priority Channel 2 TX Frequency: [blank, ???]
-// - Revert Channel: [Selected, ???]
+// - Scan Mode: [Off, On]
+// - Priority Channel Select: [Off, Priority Channel Select1, Priority Channel Select2, Priority Channel Select1 + Priority Channel Select2]
+// - Priority Channel 1: [Off, Current Channel, channel name]
+// - Priority Channel 1 RX Frequency: blank, or channel RX frequency in MHz
+// - Priority Channel 1 TX Frequency: blank, or channel TX frequency in MHz
+// - Priority Channel 2: [Off, Current Channel, channel name]
+// - Priority Channel 2 RX Frequency: blank, or channel RX frequency in MHz
+// - Priority Channel 2 TX Frequency: blank, or channel TX frequency in MHz
+// - Revert Channel: [Selected, Selected + TalkBack, Priority Channel Select1, Priority Channel Select2, Last Called, Last Used,
+//   Priority Channel Select1 + TalkBack, Priority Channel Select2 + TalkBack]
 // - Look Back Time A[s]: default 2
 // - Look Back Time B[s]: default 3
 // - Dropout Delay Time[s]: default 3.1
@@ -163,10 +220,10 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - A Channel: name of selected channel in zone
 // - A Channel RX Frequency: RX frequency in MHz of selected channel in zone
 // - A Channel TX Frequency: TX frequency in MHz of selected channel in zone
-// - B Channel: name of selected channel in zone
+// - B Channel: name of selected channel in zone (some CPS versions export this header as empty)
 // - B Channel RX Frequency: RX frequency in MHz of selected channel in zone
 // - B Channel TX Frequency: TX frequency in MHz of selected channel in zone
-// - Zone Hide: [0, ???]
+// - Zone Hide: [0, 1], header has a trailing space
 
 type CsvRecord = HashMap<String, String>;
 
@@ -177,6 +234,7 @@ fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup,
     static TALKGROUP_INDEX: AtomicUsize = AtomicUsize::new(1);
     let talkgroup = DmrTalkgroup {
         index: TALKGROUP_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         id: record.get("Radio ID").unwrap().parse::<u32>()?,
         name: record.get("Name").unwrap().to_string(),
         call_type: match record.get("Call Type").unwrap().as_str() {
@@ -186,6 +244,8 @@ fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup,
             _ => return Err(format!("Unrecognized call type: {}", record.get("Call Type").unwrap()).into()),
         },
         alert: record.get("Call Alert").unwrap() == "Online Alert",
+        timeslot_override: None, // not supported
+        extensions: None, // all columns are mapped
     };
 
     Ok(talkgroup)
@@ -196,8 +256,10 @@ fn parse_talkgroup_list_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Op
     static TALKGROUP_LIST_INDEX: AtomicUsize = AtomicUsize::new(1);
     let mut talkgroup_list = DmrTalkgroupList {
         index: TALKGROUP_LIST_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         name: record.get("Group Name").unwrap().to_string(),
         talkgroups: Vec::new(),
+        extensions: None, // all columns are mapped
     };
 
     // Talkgroup names are stored as a list, separated by "|"
@@ -214,6 +276,31 @@ fn parse_talkgroup_list_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Op
     Ok(talkgroup_list)
 }
 
+// Columns in DigitalContactList.CSV that are mapped into DmrContact (not passed through extensions)
+const CONTACT_MAPPED: [&str; 8] = [
+    "No.", "Radio ID", "Callsign", "Name", "City", "State", "Country", "Remarks",
+];
+
+// Convert the CSV digital contact hashmap into a DmrContact struct
+fn parse_contact_record(record: &CsvRecord, opt: &Opt) -> Result<DmrContact, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 4, "    {:?}", record);
+    static CONTACT_INDEX: AtomicUsize = AtomicUsize::new(1);
+    // empty columns are stored as None
+    let optional = |key: &str| record.get(key).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let contact = DmrContact {
+        index: CONTACT_INDEX.fetch_add(1, Ordering::Relaxed),
+        id: record.get("Radio ID").unwrap().parse::<u32>()?,
+        callsign: record.get("Callsign").unwrap().trim().to_string(),
+        name: optional("Name"),
+        city: optional("City"),
+        state: optional("State"),
+        country: optional("Country"),
+        remarks: optional("Remarks"),
+        extensions: super::get_extensions(record, &CONTACT_MAPPED, model()),
+    };
+    Ok(contact)
+}
+
 // Convert a string into a TxPermit enum
 fn parse_tx_permit(tx_permit: &str) -> Option<TxPermit> {
     match tx_permit {
@@ -242,6 +329,49 @@ fn parse_tone(tone: &str) -> Option<Tone> {
     return Some(Tone::Ctcss(tone.parse::<f64>().unwrap()));
 }
 
+// Convert an On/Off string into a bool, None if the column is missing
+fn parse_on_off(value: Option<&String>) -> Option<bool> {
+    value.map(|x| x == "On")
+}
+
+// Columns in Channel.CSV that are mapped into Channel (not passed through extensions)
+const CHANNEL_MAPPED: [&str; 12] = [
+    "No.", "Channel Name", "Receive Frequency", "Transmit Frequency", "Channel Type", "Transmit Power",
+    "Contact", "Contact Call Type", "Contact TG/DMR ID", "Radio ID", "Busy Lock/TX Permit", "PTT Prohibit",
+];
+// Columns only mapped for channels with FM or DMR settings, the writer fills in defaults otherwise
+const CHANNEL_MAPPED_FM: [&str; 4] = ["Band Width", "CTCSS/DCS Decode", "CTCSS/DCS Encode", "Squelch Mode"];
+const CHANNEL_MAPPED_DMR: [&str; 8] = [
+    "Color Code", "Slot", "Receive Group List", "Reverse", "Simplex TDMA", "Slot Suit", "Talk Around(Simplex)",
+    "Send Talker Alias",
+];
+// Columns other CPS versions name differently (see Channel.CSV above),
+// stored in extensions under the name this writer uses so they are written back
+const CHANNEL_ALIASES: [(&str, &str); 4] = [
+    ("TDMA", "Simplex TDMA"),
+    ("TDMA Adaptive", "Slot Suit"),
+    ("Simplex", "Through Mode"),
+    ("Exclude Channel From Roaming", "Exclude channel from roaming"),
+];
+
+// Columns mapped for this channel, Scan List and Custom CTCSS are only mapped when the channel sets them
+fn channel_mapped(channel: &Channel) -> Vec<&'static str> {
+    let mut mapped = CHANNEL_MAPPED.to_vec();
+    if channel.fm.is_some() {
+        mapped.extend(CHANNEL_MAPPED_FM);
+    }
+    if channel.dmr.is_some() {
+        mapped.extend(CHANNEL_MAPPED_DMR);
+    }
+    if let Some(Scan::ScanList(_)) = channel.scan {
+        mapped.push("Scan List");
+    }
+    if get_custom_ctcss(channel).is_some() {
+        mapped.push("Custom CTCSS");
+    }
+    mapped
+}
+
 // Convert the CSV channel hashmap into a Channel struct
 fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
@@ -249,10 +379,12 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
 
     channel.index = record.get("No.").unwrap().parse::<usize>()?;
     channel.name = record.get("Channel Name").unwrap().to_string();
-    channel.mode = match record.get("Channel Type").unwrap().as_str() {
-        "A-Analog" => ChannelMode::FM,
-        "D-Digital" => ChannelMode::DMR,
-        _ => return Err(format!("Unrecognized channel type: {}", record.get("Channel Type").unwrap()).into()),
+    // mixed channels receive both modes, and transmit in the first
+    let channel_type = record.get("Channel Type").unwrap().as_str();
+    channel.mode = match channel_type {
+        "A-Analog" | "A+D TX A" => ChannelMode::FM,
+        "D-Digital" | "D+A TX D" => ChannelMode::DMR,
+        _ => return Err(format!("Unrecognized channel type: {}", channel_type).into()),
     };
     channel.frequency_rx = Decimal::from_str(record.get("Receive Frequency").unwrap())? * Decimal::new(1_000_000, 0);
     channel.frequency_tx = Decimal::from_str(record.get("Transmit Frequency").unwrap())? * Decimal::new(1_000_000, 0);
@@ -279,7 +411,7 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
         "None" => None,
         _ => Some(Scan::ScanList(record.get("Scan List").unwrap().to_string())),
     };
-    if channel.mode == ChannelMode::FM { // FM specific fields
+    if channel_type != "D-Digital" { // FM specific fields
         channel.fm = Some(FmChannel {
             bandwidth: match record.get("Band Width").unwrap().as_str() {
                 "12.5K" => Decimal::from_str("12.5").unwrap() * Decimal::new(1_000, 0),
@@ -297,9 +429,17 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
             // null out the tone
             channel.fm.as_mut().unwrap().tone_rx = None;
         }
-    } else if channel.mode == ChannelMode::DMR { // DMR specific fields
+    }
+    if channel_type != "A-Analog" { // DMR specific fields
+        let timeslot = record.get("Slot").unwrap().parse::<u8>()?;
+        // talker alias is only sent on the channel's timeslot
+        let talker_alias = match record.get("Send Talker Alias").map(|x| x.as_str()) {
+            Some("1") => Some(TalkerAlias::Text),
+            Some("0") => Some(TalkerAlias::Off),
+            _ => None,
+        };
         channel.dmr = Some(DmrChannel {
-            timeslot: record.get("Slot").unwrap().parse::<u8>()?,
+            timeslot,
             color_code: record.get("Color Code").unwrap().parse::<u8>()?,
             // digital channels will always have Contact set (name of a talkgroup/group or private call),
             // and optionally will have Receive Group List set (name of a talkgroup list) or "None" if no list
@@ -310,10 +450,24 @@ fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dy
                 Some(record.get("Receive Group List").unwrap().to_string())
             },
             id_name: Some(record.get("Radio ID").unwrap().to_string()),
+            talker_alias_ts1: if timeslot == 1 { talker_alias.clone() } else { None },
+            talker_alias_ts2: if timeslot == 2 { talker_alias } else { None },
+            // the D578 CPS calls this TDMA
+            simplex_tdma: parse_on_off(record.get("Simplex TDMA").or(record.get("TDMA"))),
+            // older CPS versions call this TDMA Adaptive
+            tdma_adaptive: parse_on_off(record.get("Slot Suit").or(record.get("TDMA Adaptive"))),
+            dcdm_leader: None, // not supported
+            talkaround: parse_on_off(record.get("Talk Around(Simplex)")),
+            reverse: parse_on_off(record.get("Reverse")),
         })
-    } else {
-        return Err("Unparsed channel mode".into());
     }
+    let mut columns = record.clone();
+    for (alias, name) in CHANNEL_ALIASES {
+        if let Some(value) = columns.remove(alias) {
+            columns.insert(name.to_string(), value);
+        }
+    }
+    channel.extensions = super::get_extensions(&columns, &channel_mapped(&channel), model());
 
     Ok(channel)
 }
@@ -324,8 +478,15 @@ fn parse_zone_record(csv_zone: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Re
     static ZONE_INDEX: AtomicUsize = AtomicUsize::new(1);
     let mut zone = Zone {
         index: ZONE_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         name: csv_zone.get("Zone Name").unwrap().to_string(),
         channels: Vec::new(),
+        a_channel: csv_zone.get("A Channel").filter(|x| !x.is_empty()).cloned(),
+        // some CPS versions export this column with an empty header
+        b_channel: csv_zone.get("B Channel").or(csv_zone.get("")).filter(|x| !x.is_empty()).cloned(),
+        // the CPS exports this column with a trailing space
+        hidden: csv_zone.get("Zone Hide ").or(csv_zone.get("Zone Hide")).map(|x| x == "1"),
+        extensions: None, // all columns are mapped
     };
 
     // Channels are stored as a list of names, separated by "|"
@@ -342,6 +503,30 @@ fn parse_zone_record(csv_zone: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Re
     Ok(zone)
 }
 
+// Convert a priority channel string into a ScanListChannel struct
+fn parse_scan_list_channel(channel: &str) -> Option<ScanListChannel> {
+    match channel {
+        "Off" | "" => None,
+        "Current Channel" => Some(ScanListChannel::Selected),
+        _ => Some(ScanListChannel::Channel(channel.to_string())),
+    }
+}
+
+// Convert a revert channel string into a ScanRevert struct
+fn parse_scan_revert(revert: &str) -> Option<ScanRevert> {
+    match revert {
+        "Selected" => Some(ScanRevert::Selected),
+        "Selected + TalkBack" => Some(ScanRevert::SelectedTalkback),
+        "Priority Channel Select1" => Some(ScanRevert::Priority1),
+        "Priority Channel Select2" => Some(ScanRevert::Priority2),
+        "Priority Channel Select1 + TalkBack" => Some(ScanRevert::Priority1Talkback),
+        "Priority Channel Select2 + TalkBack" => Some(ScanRevert::Priority2Talkback),
+        "Last Called" => Some(ScanRevert::LastCalled),
+        "Last Used" => Some(ScanRevert::LastUsed),
+        _ => None,
+    }
+}
+
 // Convert the CSV scanlist hashmap into a ScanList struct
 fn parse_scanlist_record(csv_scanlist: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Result<ScanList, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", csv_scanlist);
@@ -350,8 +535,24 @@ fn parse_scanlist_record(csv_scanlist: &CsvRecord, codeplug: &Codeplug, opt: &Op
         index: SCANLIST_INDEX.fetch_add(1, Ordering::Relaxed),
         name: csv_scanlist.get("Scan List Name").unwrap().to_string(),
         channels: Vec::new(),
+        scan_mode: Some(csv_scanlist.get("Scan Mode").unwrap() != "Off"),
+        revert_channel: parse_scan_revert(csv_scanlist.get("Revert Channel").unwrap()),
+        look_back_time_a: csv_scanlist.get("Look Back Time A[s]").unwrap().parse::<f64>().ok(),
+        look_back_time_b: csv_scanlist.get("Look Back Time B[s]").unwrap().parse::<f64>().ok(),
+        dropout_delay_time: csv_scanlist.get("Dropout Delay Time[s]").unwrap().parse::<f64>().ok(),
+        dwell_time: csv_scanlist.get("Dwell Time[s]").unwrap().parse::<f64>().ok(),
+        ..Default::default()
     };
 
+    // Priority channels are only set if enabled by Priority Channel Select
+    let priority_select = csv_scanlist.get("Priority Channel Select").unwrap();
+    if priority_select.contains("Select1") {
+        scanlist.priority_channel_1 = parse_scan_list_channel(csv_scanlist.get("Priority Channel 1").unwrap());
+    }
+    if priority_select.contains("Select2") {
+        scanlist.priority_channel_2 = parse_scan_list_channel(csv_scanlist.get("Priority Channel 2").unwrap());
+    }
+
     // Channels are stored as a list of names, separated by "|"
     let channel_names: Vec<&str> = csv_scanlist.get("Scan Channel Member").unwrap().split('|').collect();
     for name in channel_names {
@@ -366,6 +567,34 @@ fn parse_scanlist_record(csv_scanlist: &CsvRecord, codeplug: &Codeplug, opt: &Op
     Ok(scanlist)
 }
 
+// Convert the CSV FM/AMAir hashmap into a ReceiveMemory struct
+fn parse_receive_memory_record(record: &CsvRecord, opt: &Opt) -> Result<ReceiveMemory, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 4, "    {:?}", record);
+    let memory = ReceiveMemory {
+        index: record.get("No.").unwrap().parse::<usize>()?,
+        // only AMAir.CSV has names
+        name: match record.get("Name") {
+            Some(name) => name.trim_matches(|c: char| c == '\0' || c.is_whitespace()).to_string(),
+            None => "".to_string(),
+        },
+        frequency: Decimal::from_str(record.get("Frequency[MHz]").unwrap())? * Decimal::new(1_000_000, 0),
+        scan: matches!(record.get("Scan").unwrap().as_str(), "Add" | "1"),
+    };
+
+    Ok(memory)
+}
+
+// Convert an offset frequency string ("600.00 KHz" or "5.00000 MHz") into Hz
+fn parse_offset_frequency(offset: &str) -> Result<Decimal, Box<dyn Error>> {
+    let (value, unit) = offset.trim().split_once(' ').ok_or(format!("Unrecognized offset frequency: {}", offset))?;
+    let value = Decimal::from_str(value)?;
+    match unit {
+        "KHz" => Ok(value * Decimal::new(1_000, 0)),
+        "MHz" => Ok(value * Decimal::new(1_000_000, 0)),
+        _ => Err(format!("Unrecognized offset frequency: {}", offset).into()),
+    }
+}
+
 // Convert the CSV DMR ID hashmap into a DMRId struct
 fn parse_dmr_id_record(csv_dmr_id: &CsvRecord, opt: &Opt) -> Result<DmrId, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", csv_dmr_id);
@@ -377,6 +606,243 @@ fn parse_dmr_id_record(csv_dmr_id: &CsvRecord, opt: &Opt) -> Result<DmrId, Box<d
     Ok(dmr_id)
 }
 
+// Columns in OptionalSetting.CSV, with the values exported by an unconfigured CPS
+// these are used for any settings we don't have a value for when writing
+const OPTIONAL_SETTING_DEFAULTS: [(&str, &str); 173] = [
+    ("Beep", "0"),
+    ("DSP", "0"),
+    ("KeyLock", "0"),
+    ("AutoPowerOff", "0"),
+    ("TOT", "0"),
+    ("Language", "0"),
+    ("StartDspSet", "0"),
+    ("Password", "0"),
+    ("Step", "0"),
+    ("SQL1", "0"),
+    ("SQL2", "0"),
+    ("PowerSave", "0"),
+    ("VOX", "0"),
+    ("VOX_Delay", "0"),
+    ("ScanType", "0"),
+    ("MicLevel", "0"),
+    ("RMV1", "0"),
+    ("RMV2", "0"),
+    ("STE_Type", "0"),
+    ("STE_Freq", "0"),
+    ("GroupTalkHold", "0"),
+    ("PersonTalkHold", "0"),
+    ("PreWave", "0"),
+    ("Preamble", "0"),
+    ("Work_FMCH", "0"),
+    ("FM_VFO", "0"),
+    ("Work_Zone1", "0"),
+    ("Work_Zone2", "0"),
+    ("FM_En", "0"),
+    ("Record_En", "0"),
+    ("FailAlarm", "0"),
+    ("MonType", "0"),
+    ("Lightness", "0"),
+    ("AutoBKLightTime", "0"),
+    ("Gps", "0"),
+    ("MsgRing", "0"),
+    ("FreqDis", "0"),
+    ("FmMon", "0"),
+    ("MainState", "0"),
+    ("SubMode", "0"),
+    ("TBST", "0"),
+    ("CallRing", "0"),
+    ("TmZone", "0"),
+    ("TalkTips", "0"),
+    ("OverVoice", "0"),
+    ("VoxHeadset", "0"),
+    ("Start_Char", ""),
+    ("Reserved_Start_Char", ""),
+    ("Password_Char", ""),
+    ("Reserved_EMG_Kind", "0"),
+    ("Voice_Note", "0"),
+    ("SqOnVoice", "0"),
+    ("IdleWait", "0"),
+    ("MissCallFilter", "0"),
+    ("Boot_Sound", "0"),
+    ("TalkOverPoint", "0"),
+    ("MaxVol", "0"),
+    ("DigiStunKillEn", "0"),
+    ("Reserved_DigiKillEn", "0"),
+    ("RemoteMoniEn", "0"),
+    ("GpsReplyEn", "0"),
+    ("ContactOutSetEn", "0"),
+    ("Start_Char2", ""),
+    ("PfLongTime", "0"),
+    ("VolNoteEn", "0"),
+    ("AutoRepeater", "0"),
+    ("DigiMoni", "0"),
+    ("AprsUpDate", "0"),
+    ("AprsTxFreq", "0"),
+    ("ManDownWait", "0"),
+    ("AnaHoldTime", "0"),
+    ("DateDisKind", "0"),
+    ("VfoScanFreq0", "0"),
+    ("VfoScanFreq1", "0"),
+    ("VfoScanFreq2", "0"),
+    ("VfoScanFreq3", "0"),
+    ("UhfAutoRep", "0"),
+    ("VhfAutoRep", "0"),
+    ("CurTalkPathHold", "0"),
+    ("PriZoneA", "0"),
+    ("PriZoneB", "0"),
+    ("EnSoundEffect", "0"),
+    ("MsgOacsuSet", "0"),
+    ("DigiMoniCc", "0"),
+    ("DigiMoniId", "0"),
+    ("LastCallDis", "0"),
+    ("EarMaxVol", "0"),
+    ("GpsTextUsed", "0"),
+    ("RecordDelay", "0"),
+    ("CallModeDisKind", "0"),
+    ("BlueToothOn", "0"),
+    ("MicInBlueTooth", "0"),
+    ("SpkInBlueTooth", "0"),
+    ("WtRecordNote", "0"),
+    ("MeasurePeriod", "0"),
+    ("BhtMicGain", "0"),
+    ("BhtSpkGain", "0"),
+    ("ChanNumDisKind", "0"),
+    ("MenuDisWorkContact", "0"),
+    ("WanderPerod", "0"),
+    ("Ext_Opt1", "0"),
+    ("WanderEffectWait", "0"),
+    ("Ext_Opt2", "0"),
+    ("Scan_Pri_Current", "0"),
+    ("GroupCallS_Ext", "0"),
+    ("Contact_Compression", "0"),
+    ("RX_Contact_Compression", "0"),
+    ("File_Optimization", "0"),
+    ("AutoRepFreq0", "136"),
+    ("AutoRepFreq1", "174"),
+    ("AutoRepFreq2", "400"),
+    ("AutoRepFreq3", "520"),
+    ("AutoRepeaterB", "0"),
+    ("BookOwnId", "0"),
+    ("AprsDistanceDis", "0"),
+    ("StartChUse", "0"),
+    ("StartZone1", "0"),
+    ("StartZone2", "0"),
+    ("StartCurChan1", "0"),
+    ("StartCurChan2", "0"),
+    ("MenuLowPower", "0"),
+    ("BsModeCheck", "0"),
+    ("TimeBsCheck", "0"),
+    ("BsCheckTimes", "0"),
+    ("FixRomanStartOp", "0"),
+    ("TxDimWait", "0"),
+    ("DiviDisEn", "0"),
+    ("LastHeardChanSet", "0"),
+    ("ChanNameColour", "0"),
+    ("OutRepNote", "0"),
+    ("RxDimWait", "0"),
+    ("TimeRoamOn", "0"),
+    ("CurRoamZone", "0"),
+    ("RoamEffectChanDis", "0"),
+    ("FixTimeMute", "0"),
+    ("comVersion", "0"),
+    ("OutNoteTimes", "0"),
+    ("StartTestGps", "0"),
+    ("StartResetEn", "1"),
+    ("BhtHoldTime", "10"),
+    ("BhtHoldDelay", "2"),
+    ("WxAlarmSign", "0"),
+    ("BhtPttHold", "0"),
+    ("UhfAutoRep2", "2"),
+    ("VhfAutoRep2", "1"),
+    ("CurUhfAutoRep", "0"),
+    ("CurVhfAutoRep", "0"),
+    ("PttSleepTime", "1"),
+    ("GpsMode", "2"),
+    ("SteTime", "25"),
+    ("CallSignColour", "4"),
+    ("AutoRepMinFreqV2", "146"),
+    ("AutoRepMaxFreqV2", "164"),
+    ("AutoRepMinFreqU2", "410"),
+    ("autoRepMaxFreqU2", "470"),
+    ("DialGroupHold", "3"),
+    ("DialPrivateHold", "3"),
+    ("ChanNameColourB", "0"),
+    ("DigiEmgKind", "1"),
+    ("WorkCharDisColour", "0"),
+    ("bkpic", "0"),
+    ("TotPreEn", "1"),
+    ("TxAgcCon", "1"),
+    ("ZoneNameColourA", "2"),
+    ("ZoneNameColourB", "2"),
+    ("ApoKind", "0"),
+    ("SmsFormat", "0"),
+    ("ChanDisCtr", "7"),
+    ("AnaSqOnVoice", "0"),
+    ("DateDisFormat", "0"),
+    ("AnaMic", "0"),
+    ("ZoneBarsEn", "0"),
+    ("VcallRpheader", "0"),
+];
+
+// Columns in OptionalSetting.CSV that are mapped into GeneralSettings (not passed through raw)
+const OPTIONAL_SETTING_MAPPED: [&str; 10] = [
+    "Beep", "TOT", "SQL1", "SQL2", "PowerSave", "VOX", "MicLevel", "AutoBKLightTime", "Start_Char", "Start_Char2",
+];
+
+// Backlight timeout options in seconds, indexed by AutoBKLightTime (0 is always on)
+const BACKLIGHT_TIMEOUTS: [u32; 16] = [0, 5, 10, 15, 20, 25, 30, 60, 120, 180, 240, 300, 900, 1800, 2700, 3600];
+
+// Convert the CSV optional setting hashmap into a GeneralSettings struct
+fn parse_general_settings_record(record: &CsvRecord, opt: &Opt) -> Result<GeneralSettings, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 4, "    {:?}", record);
+    let mut settings = GeneralSettings::default();
+
+    // parse a numeric setting, treating a missing or empty column as unset
+    let get_u32 = |key: &str| -> Result<Option<u32>, Box<dyn Error>> {
+        match record.get(key).map(|s| s.trim()) {
+            Some(value) if !value.is_empty() => Ok(Some(value.parse::<u32>()?)),
+            _ => Ok(None),
+        }
+    };
+
+    settings.intro_line_1 = record.get("Start_Char").filter(|s| !s.is_empty()).map(|s| s.to_string());
+    settings.intro_line_2 = record.get("Start_Char2").filter(|s| !s.is_empty()).map(|s| s.to_string());
+    settings.beep = get_u32("Beep")?.map(|v| v != 0);
+    settings.tx_tot = match get_u32("TOT")? {
+        Some(0) => Timeout::Infinite,
+        Some(v) => Timeout::Seconds(v * 30),
+        None => Timeout::Default,
+    };
+    // MicLevel 0..4 maps onto 1..10
+    settings.mic_level = get_u32("MicLevel")?.map(|v| (v.min(4) * 2 + 2) as u8);
+    // VOX 0..3 maps onto 0..10
+    settings.vox_level = get_u32("VOX")?.map(|v| (v.min(3) * 3) as u8);
+    // SQL 0..5 maps onto 0..100%, only VFO A is used
+    settings.squelch = match get_u32("SQL1")? {
+        Some(v) => Squelch::Percent((v.min(5) * 20) as u8),
+        None => Squelch::Default,
+    };
+    settings.power_save = get_u32("PowerSave")?.map(|v| v != 0);
+    settings.backlight_timeout = match get_u32("AutoBKLightTime")? {
+        Some(0) => Timeout::Infinite,
+        Some(v) => Timeout::Seconds(BACKLIGHT_TIMEOUTS[(v as usize).min(BACKLIGHT_TIMEOUTS.len() - 1)]),
+        None => Timeout::Default,
+    };
+
+    // keep everything else as raw settings
+    let mut raw = BTreeMap::new();
+    for (key, value) in record {
+        if !OPTIONAL_SETTING_MAPPED.contains(&key.as_str()) {
+            raw.insert(key.clone(), value.clone());
+        }
+    }
+    if !raw.is_empty() {
+        settings.raw = Some(raw);
+    }
+
+    Ok(settings)
+}
+
 pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 4, "props = {:?}", get_props());
@@ -428,6 +894,22 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
         }
     }
 
+    // Check for DigitalContactList.CSV
+    let mut contacts_path: PathBuf = input_path.clone();
+    contacts_path.push("DigitalContactList.CSV");
+    // if this file doesn't exist, no problem, we just don't have any contacts
+    if contacts_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", contacts_path.display());
+        let mut reader = csv::Reader::from_path(contacts_path)?;
+        for result in reader.deserialize() {
+            let record: CsvRecord = result?;
+            // convert from CSV record to DmrContact struct
+            let contact = parse_contact_record(&record, opt)?;
+            // append to codeplug.contacts
+            codeplug.contacts.push(contact);
+        }
+    }
+
     // Check for Channel.CSV
     let mut channels_path: PathBuf = input_path.clone();
     channels_path.push("Channel.CSV");
@@ -482,6 +964,38 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
         }
     }
 
+    // Check for FM.CSV
+    let mut broadcast_path: PathBuf = input_path.clone();
+    broadcast_path.push("FM.CSV");
+    // if FM.CSV doesn't exist, no problem, we just don't have any broadcast memories
+    if broadcast_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", broadcast_path.display());
+        let mut reader = csv::Reader::from_path(broadcast_path)?;
+        for result in reader.deserialize() {
+            let record: CsvRecord = result?;
+            // convert from CSV record to ReceiveMemory struct
+            let memory = parse_receive_memory_record(&record, opt)?;
+            // append to codeplug.broadcast_memories
+            codeplug.broadcast_memories.push(memory);
+        }
+    }
+
+    // Check for AMAir.CSV
+    let mut airband_path: PathBuf = input_path.clone();
+    airband_path.push("AMAir.CSV");
+    // if AMAir.CSV doesn't exist, no problem, only the D578 has airband
+    if airband_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", airband_path.display());
+        let mut reader = csv::Reader::from_path(airband_path)?;
+        for result in reader.deserialize() {
+            let record: CsvRecord = result?;
+            // convert from CSV record to ReceiveMemory struct
+            let memory = parse_receive_memory_record(&record, opt)?;
+            // append to codeplug.airband_memories
+            codeplug.airband_memories.push(memory);
+        }
+    }
+
     // Check for RadioIDList.CSV
     let mut radio_id_list_path: PathBuf = input_path.clone();
     radio_id_list_path.push("RadioIDList.CSV");
@@ -507,12 +1021,61 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
                     dmr_configuration: Some(DmrConfiguration {
                         id_list: Vec::new(),
                     }),
+                    general_settings: None,
+                    auto_repeater_offsets: None,
                 });
             }
             codeplug.config.as_mut().unwrap().dmr_configuration.as_mut().unwrap().id_list.push(dmr_id);
         }
     }
 
+    // Check for AutoRepeaterOffsetFrequencys.CSV
+    let mut offsets_path: PathBuf = input_path.clone();
+    offsets_path.push("AutoRepeaterOffsetFrequencys.CSV");
+    // if this file doesn't exist, no problem, the offsets will come from the bandplan
+    if offsets_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", offsets_path.display());
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_path(offsets_path)?;
+        let mut offsets: Vec<Decimal> = Vec::new();
+        for result in reader.records() {
+            let record = result?;
+            // the second column is the offset, ignore any unnamed trailing columns
+            offsets.push(parse_offset_frequency(record.get(1).unwrap_or(""))?);
+        }
+        if codeplug.config.is_none() {
+            codeplug.config = Some(Configuration {
+                dmr_configuration: None,
+                general_settings: None,
+                auto_repeater_offsets: None,
+            });
+        }
+        codeplug.config.as_mut().unwrap().auto_repeater_offsets = Some(offsets);
+    }
+    // Check for OptionalSetting.CSV
+    let mut optional_setting_path: PathBuf = input_path.clone();
+    optional_setting_path.push("OptionalSetting.CSV");
+    // if this file doesn't exist, no problem, we just don't have any general settings
+    if optional_setting_path.exists() {
+        uprintln!(opt, Stderr, None, 3, "Reading {}", optional_setting_path.display());
+        let mut reader = csv::Reader::from_path(optional_setting_path)?;
+        // there should only be one row
+        if let Some(result) = reader.deserialize().next() {
+            let record: CsvRecord = result?;
+            // convert from CSV record to GeneralSettings struct
+            let general_settings = parse_general_settings_record(&record, opt)?;
+            if codeplug.config.is_none() {
+                codeplug.config = Some(Configuration {
+                    dmr_configuration: None,
+                    general_settings: None,
+                    auto_repeater_offsets: None,
+                });
+            }
+            codeplug.config.as_mut().unwrap().general_settings = Some(general_settings);
+        }
+    }
+
     Ok(codeplug)
 }
 
@@ -598,6 +1161,55 @@ pub fn write_talkgroup_lists(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) ->
     Ok(())
 }
 
+pub fn write_contacts(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Anytone CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    let header = [
+        "No.",
+        "Radio ID",
+        "Callsign",
+        "Name",
+        "City",
+        "State",
+        "Country",
+        "Remarks",
+        "Call Type",
+        "Call Alert",
+    ];
+    writer.write_record(header)?;
+
+    let mut dropped = BTreeSet::new();
+    for (ii, contact) in codeplug.contacts.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing contact {:width$}: {}", contact.id, contact.callsign, width = 8);
+        let mut record = [
+            format!("{}", ii + 1), // No.
+            contact.id.to_string(), // Radio ID
+            contact.callsign.clone(), // Callsign
+            contact.name.clone().unwrap_or_default(), // Name
+            contact.city.clone().unwrap_or_default(), // City
+            contact.state.clone().unwrap_or_default(), // State
+            contact.country.clone().unwrap_or_default(), // Country
+            contact.remarks.clone().unwrap_or_default(), // Remarks
+            "Private Call".to_string(), // Call Type
+            "None".to_string(), // Call Alert
+        ];
+        super::apply_extensions(&header, &mut record, &CONTACT_MAPPED, &contact.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
+    }
+
+    writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
+
+    Ok(())
+}
+
 fn write_power(power: &Power) -> String {
     match power {
         Power::Default => "High".to_string(), // 5W
@@ -674,6 +1286,38 @@ fn write_radio_id(channel: &Channel, codeplug: &Codeplug) -> String {
     "".to_string()
 }
 
+fn write_talker_alias(channel: &Channel) -> String {
+    let dmr = channel.dmr.as_ref().unwrap();
+    let talker_alias = if dmr.timeslot == 1 { &dmr.talker_alias_ts1 } else { &dmr.talker_alias_ts2 };
+    match talker_alias {
+        Some(TalkerAlias::Off) | None => "0".to_string(),
+        Some(_) => "1".to_string(),
+    }
+}
+
+fn write_on_off(value: Option<bool>) -> String {
+    if value == Some(true) { "On" } else { "Off" }.to_string()
+}
+
+fn write_tone(tone: &Option<Tone>) -> String {
+    match tone {
+        Some(Tone::Ctcss(ctcss)) => format!("{:0.1}", ctcss),
+        Some(Tone::Dcs(dcs)) => dcs.clone(),
+        None => "Off".to_string(),
+    }
+}
+
+// Mixed channels have both fm and dmr set, with channel.mode as the TX mode
+fn write_channel_type(channel: &Channel) -> String {
+    let mixed = channel.fm.is_some() && channel.dmr.is_some();
+    match channel.mode {
+        ChannelMode::DMR if mixed => "D+A TX D",
+        ChannelMode::DMR => "D-Digital",
+        _ if mixed => "A+D TX A",
+        _ => "A-Analog",
+    }.to_string()
+}
+
 fn write_tx_permit(channel: &Channel) -> String {
     let tx_permit = match &channel.tx_permit {
         Some(TxPermit::Always) => "Always".to_string(),
@@ -692,24 +1336,32 @@ fn write_tx_permit(channel: &Channel) -> String {
     tx_permit
 }
 
-fn write_custom_ctcss(channel: &Channel) -> String {
+fn get_custom_ctcss(channel: &Channel) -> Option<f64> {
     // if the CTCSS frequency is below 62.5 or above 254.1, write it as a custom frequency
     // @TODO this is an imperfect solution, but it works for now
     // we should be validating against a list of valid CTCSS frequencies
-    if let Some(tone_rx) = &channel.fm.as_ref().unwrap().tone_rx {
-        if let Tone::Ctcss(ctcss) = tone_rx {
-            if *ctcss < 62.5 || *ctcss > 254.1 {
-                return format!("{:0.1}", ctcss);
-            }
+    if let Some(Tone::Ctcss(ctcss)) = channel.fm.as_ref().and_then(|fm| fm.tone_rx.as_ref()) {
+        if *ctcss < 62.5 || *ctcss > 254.1 {
+            return Some(*ctcss);
         }
     }
-    "251.1".to_string()
+    None
+}
+
+fn write_custom_ctcss(channel: &Channel) -> String {
+    match get_custom_ctcss(channel) {
+        Some(ctcss) => format!("{:0.1}", ctcss),
+        None => "251.1".to_string(),
+    }
 }
 
 // scan list needs to be set in the channel
-// right now, we build the scan list from the zone
+// use the channel's scan list if it has one, otherwise build the scan list from the zone
 // so just pick the first zone that contains the channel, and set that as the scan list (if it exists)
 fn write_scan_list(channel: &Channel, codeplug: &Codeplug) -> String {
+    if let Some(Scan::ScanList(name)) = &channel.scan {
+        return name.clone();
+    }
     for zone in &codeplug.zones {
         if zone.channels.contains(&channel.name) {
             return zone.name.clone();
@@ -737,7 +1389,7 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
         .from_path(path)?;
 
     // write the header
-    writer.write_record(&[
+    let header = [
         "No.",
         "Channel Name",
         "Receive Frequency",
@@ -793,38 +1445,26 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
         "AnaAprsTxPath",
         "ARC4",
         "ex_emg_kind",
-    ])?;
+    ];
+    writer.write_record(header)?;
 
+    let mut dropped = BTreeSet::new();
     for channel in &codeplug.channels {
         uprintln!(opt, Stderr, None, 4, "Writing channel {:width$}: {}", channel.index, channel.name, width = get_props().channel_index_width);
         uprintln!(opt, Stderr, None, 4, "    {:?}", channel);
 
         let contact = get_contact_tuple(&channel, &codeplug);
-        if channel.mode == ChannelMode::FM {
-            writer.write_record(&[
+        let mut record = if channel.mode == ChannelMode::FM {
+            [
                 channel.index.to_string(), // No.
                 channel.name.clone(), // Channel Name
                 format!("{:0.5}", (channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Receive Frequency
                 format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Transmit Frequency
-                "A-Analog".to_string(), // Channel Type
+                write_channel_type(channel), // Channel Type
                 write_power(&channel.power), // Transmit Power
                 format!("{}K", (channel.fm.as_ref().unwrap().bandwidth / Decimal::new(1_000, 0)).to_f64().unwrap()), // Band Width
-                if let Some(tone) = &channel.fm.as_ref().unwrap().tone_rx {
-                    match tone {
-                        Tone::Ctcss(freq) => format!("{:.1}", freq),
-                        Tone::Dcs(code) => code.to_string(),
-                    }
-                } else {
-                    "Off".to_string()
-                }, // CTCSS/DCS Decode
-                if let Some(tone) = &channel.fm.as_ref().unwrap().tone_tx {
-                    match tone {
-                        Tone::Ctcss(freq) => format!("{:.1}", freq),
-                        Tone::Dcs(code) => code.to_string(),
-                    }
-                } else {
-                    "Off".to_string()
-                }, // CTCSS/DCS Encode
+                write_tone(&channel.fm.as_ref().unwrap().tone_rx), // CTCSS/DCS Decode
+                write_tone(&channel.fm.as_ref().unwrap().tone_tx), // CTCSS/DCS Encode
                 contact.0, // Contact
                 contact.1, // Contact Call Type
                 contact.2, // Contact TG/DMR ID
@@ -840,18 +1480,18 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 "1".to_string(), // 2Tone ID
                 "1".to_string(), // 5Tone ID
                 "Off".to_string(), // PTT ID
-                "1".to_string(), // Color Code (this has to be set on analog channels or the CPS will refuse to import)
-                "1".to_string(), // Slot
+                channel.dmr.as_ref().map_or("1".to_string(), |dmr| dmr.color_code.to_string()), // Color Code (this has to be set on analog channels or the CPS will refuse to import)
+                channel.dmr.as_ref().map_or("1".to_string(), |dmr| dmr.timeslot.to_string()), // Slot
                 write_scan_list(&channel, &codeplug), // Scan List
                 write_receive_group_list(&channel, &codeplug), // Receive Group List
                 if channel.rx_only { "On" } else { "Off" }.to_string(), // PTT Prohibit
-                "Off".to_string(), // Reverse
-                "Off".to_string(), // Simplex TDMA
-                "Off".to_string(), // Slot Suit
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.reverse)), // Reverse
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.simplex_tdma)), // Simplex TDMA
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.tdma_adaptive)), // Slot Suit
                 "Normal Encryption".to_string(), // AES Digital Encryption
                 "Off".to_string(), // Digital Encryption
                 "Off".to_string(), // Call Confirmation
-                "Off".to_string(), // Talk Around(Simplex)
+                write_on_off(channel.dmr.as_ref().and_then(|dmr| dmr.talkaround)), // Talk Around(Simplex)
                 "Off".to_string(), // Work Alone
                 write_custom_ctcss(&channel), // Custom CTCSS
                 "0".to_string(), // 2TONE Decode
@@ -871,28 +1511,32 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 "0".to_string(), // R5ToneEot
                 "0".to_string(), // Auto Scan
                 "0".to_string(), // Ana Aprs Mute
-                "0".to_string(), // Send Talker Alias
+                if channel.dmr.is_some() { write_talker_alias(channel) } else { "0".to_string() }, // Send Talker Alias
                 "0".to_string(), // AnaAprsTxPath
                 "0".to_string(), // ARC4
                 "0".to_string(), // ex_emg_kind
-            ])?;
+            ]
         } else if channel.mode == ChannelMode::DMR {
-            writer.write_record(&[
+            [
                 channel.index.to_string(), // No.
                 channel.name.clone(), // Channel Name
                 format!("{:0.5}", (channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Receive Frequency
                 format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Transmit Frequency
-                "D-Digital".to_string(), // Channel Type
+                write_channel_type(channel), // Channel Type
                 write_power(&channel.power), // Transmit Power
-                "12.5K".to_string(), // Band Width
-                "Off".to_string(), // CTCSS/DCS Decode
-                "Off".to_string(), // CTCSS/DCS Encode
+                channel.fm.as_ref().map_or("12.5K".to_string(), |fm| format!("{}K", (fm.bandwidth / Decimal::new(1_000, 0)).to_f64().unwrap())), // Band Width
+                channel.fm.as_ref().map_or("Off".to_string(), |fm| write_tone(&fm.tone_rx)), // CTCSS/DCS Decode
+                channel.fm.as_ref().map_or("Off".to_string(), |fm| write_tone(&fm.tone_tx)), // CTCSS/DCS Encode
                 contact.0, // Contact
                 contact.1, // Contact Call Type
                 contact.2, // Contact TG/DMR ID
                 write_radio_id(&channel, &codeplug), // Radio ID
                 write_tx_permit(&channel), // Busy Lock/TX Permit
-                "Carrier".to_string(), // Squelch Mode
+                if channel.fm.as_ref().is_some_and(|fm| fm.tone_rx.is_some()) {
+                    "CTCSS/DCS".to_string()
+                } else {
+                    "Carrier".to_string()
+                }, // Squelch Mode
                 "Off".to_string(), // Optional Signal
                 "1".to_string(), // DTMF ID
                 "1".to_string(), // 2Tone ID
@@ -903,15 +1547,15 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 write_scan_list(&channel, &codeplug), // Scan List
                 write_receive_group_list(&channel, &codeplug), // Receive Group List
                 if channel.rx_only { "On" } else { "Off" }.to_string(), // PTT Prohibit
-                "Off".to_string(), // Reverse
-                "Off".to_string(), // Simplex TDMA
-                "Off".to_string(), // Slot Suit
+                write_on_off(channel.dmr.as_ref().unwrap().reverse), // Reverse
+                write_on_off(channel.dmr.as_ref().unwrap().simplex_tdma), // Simplex TDMA
+                write_on_off(channel.dmr.as_ref().unwrap().tdma_adaptive), // Slot Suit
                 "Normal Encryption".to_string(), // AES Digital Encryption
                 "Off".to_string(), // Digital Encryption
                 "Off".to_string(), // Call Confirmation
-                "Off".to_string(), // Talk Around(Simplex)
+                write_on_off(channel.dmr.as_ref().unwrap().talkaround), // Talk Around(Simplex)
                 "Off".to_string(), // Work Alone
-                "251.1".to_string(), // Custom CTCSS
+                write_custom_ctcss(channel), // Custom CTCSS
                 "0".to_string(), // 2TONE Decode
                 "Off".to_string(), // Ranging
                 "Off".to_string(), // Through Mode
@@ -929,21 +1573,37 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 "0".to_string(), // R5ToneEot
                 "0".to_string(), // Auto Scan
                 "0".to_string(), // Ana Aprs Mute
-                "0".to_string(), // Send Talker Alias
+                write_talker_alias(channel), // Send Talker Alias
                 "0".to_string(), // AnaAprsTxPath
                 "0".to_string(), // ARC4
                 "0".to_string(), // ex_emg_kind
-            ])?;
+            ]
         } else {
             uprintln!(opt, Stderr, Color::Red, None, "Unsupported channel mode: index = {}, mode = {:?}", channel.index, channel.mode);
-        }
+            continue;
+        };
+        super::apply_extensions(&header, &mut record, &channel_mapped(channel), &channel.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
     }
 
     writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
 
     Ok(())
 }
 
+// Get the A and B channel names for a zone, falling back to the first and second channels in the zone
+// if unset or not in the codeplug (they should be members of the zone, validate_generic() warns if not)
+fn write_zone_ab_channels<'a>(zone: &'a Zone, codeplug: &Codeplug) -> (&'a String, &'a String) {
+    let a_channel = zone.a_channel.as_ref()
+        .filter(|x| codeplug.channels.iter().any(|c| c.name == **x))
+        .unwrap_or(&zone.channels[0]);
+    let b_channel = zone.b_channel.as_ref()
+        .filter(|x| codeplug.channels.iter().any(|c| c.name == **x))
+        .unwrap_or(zone.channels.get(1).unwrap_or(&zone.channels[0]));
+    (a_channel, b_channel)
+}
+
 pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
@@ -985,10 +1645,10 @@ pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
             channel_rx_frequencies.push_str(&format!("{:0.5}", (channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()));
             channel_tx_frequencies.push_str(&format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()));
         }
-        // get the first channel in the zone
-        let first_channel = codeplug.channels.iter().find(|&c| c.name == zone.channels[0]).unwrap();
-        // get the second channel in the zone, or the first channel if there is only one
-        let second_channel = codeplug.channels.iter().find(|&c| c.name == *zone.channels.get(1).unwrap_or(&zone.channels[0])).unwrap();
+        // use the selected A/B channels if set, otherwise the first and second channels in the zone
+        let (a_name, b_name) = write_zone_ab_channels(zone, codeplug);
+        let first_channel = codeplug.channels.iter().find(|&c| c.name == *a_name).unwrap();
+        let second_channel = codeplug.channels.iter().find(|&c| c.name == *b_name).unwrap();
         writer.write_record(&[
             format!("{}", ii + 1), // No.
             zone.name.clone(), // Zone Name
@@ -1001,7 +1661,7 @@ pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
             second_channel.name.clone(), // B Channel
             format!("{:0.5}", (second_channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // B Channel RX Frequency
             format!("{:0.5}", (second_channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // B Channel TX Frequency
-            "0".to_string(), // Zone Hide
+            if zone.hidden == Some(true) { "1" } else { "0" }.to_string(), // Zone Hide
         ])?;
     }
 
@@ -1009,6 +1669,40 @@ pub fn write_zones(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
     Ok(())
 }
 
+fn write_scan_list_channel(channel: &Option<ScanListChannel>) -> String {
+    match channel {
+        None => "Off".to_string(),
+        Some(ScanListChannel::Selected) => "Current Channel".to_string(),
+        Some(ScanListChannel::Channel(name)) => name.clone(),
+    }
+}
+
+fn write_priority_channel_select(scanlist: &ScanList) -> String {
+    match (&scanlist.priority_channel_1, &scanlist.priority_channel_2) {
+        (None, None) => "Off".to_string(),
+        (Some(_), None) => "Priority Channel Select1".to_string(),
+        (None, Some(_)) => "Priority Channel Select2".to_string(),
+        (Some(_), Some(_)) => "Priority Channel Select1 + Priority Channel Select2".to_string(),
+    }
+}
+
+fn write_scan_revert(scanlist: &ScanList, opt: &Opt) -> String {
+    match &scanlist.revert_channel {
+        None | Some(ScanRevert::Selected) => "Selected",
+        Some(ScanRevert::SelectedTalkback) => "Selected + TalkBack",
+        Some(ScanRevert::Priority1) => "Priority Channel Select1",
+        Some(ScanRevert::Priority2) => "Priority Channel Select2",
+        Some(ScanRevert::Priority1Talkback) => "Priority Channel Select1 + TalkBack",
+        Some(ScanRevert::Priority2Talkback) => "Priority Channel Select2 + TalkBack",
+        Some(ScanRevert::LastCalled) => "Last Called",
+        Some(ScanRevert::LastUsed) => "Last Used",
+        Some(ScanRevert::Channel(name)) => {
+            uprintln!(opt, Stderr, Color::Yellow, None, "Scan list {}: revert to a fixed channel ({}) not supported, using Selected", scanlist.name, name);
+            "Selected"
+        },
+    }.to_string()
+}
+
 pub fn write_scanlists(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
@@ -1058,25 +1752,42 @@ pub fn write_scanlists(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result
             channel_tx_frequencies.push_str(&format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()));
         }
 
+        // priority channels that are a fixed channel also carry its frequencies
+        let priority_frequencies: Vec<(String, String)> = [&scanlist.priority_channel_1, &scanlist.priority_channel_2].iter().map(|pc| {
+            match pc {
+                Some(ScanListChannel::Channel(name)) => match codeplug.channels.iter().find(|&c| c.name == *name) {
+                    Some(channel) => (
+                        format!("{:0.5}", (channel.frequency_rx / Decimal::new(1_000_000, 0)).to_f64().unwrap()),
+                        format!("{:0.5}", (channel.frequency_tx / Decimal::new(1_000_000, 0)).to_f64().unwrap()),
+                    ),
+                    None => {
+                        uprintln!(opt, Stderr, Color::Yellow, None, "Scan list {}: priority channel not found: {}", scanlist.name, name);
+                        ("".to_string(), "".to_string())
+                    },
+                },
+                _ => ("".to_string(), "".to_string()),
+            }
+        }).collect();
+
         writer.write_record(&[
             format!("{}", ii + 1), // No.
             scanlist.name.clone(), // Scan List Name
             channel_names, // Scan Channel Member
             channel_rx_frequencies, // Scan Channel Member RX Frequency
             channel_tx_frequencies, // Scan Channel Member TX Frequency
-            "Off".to_string(), // Scan Mode
-            "Off".to_string(), // Priority Channel Select
-            "Off".to_string(), // Priority Channel 1
-            "".to_string(), // Priority Channel 1 RX Frequency
-            "".to_string(), // Priority Channel 1 TX Frequency
-            "Off".to_string(), // Priority Channel 2
-            "".to_string(), // Priority Channel 2 RX Frequency
-            "".to_string(), // Priority Channel 2 TX Frequency
-            "Selected".to_string(), // Revert Channel
-            "2.0".to_string(), // Look Back Time A[s]
-            "3.0".to_string(), // Look Back Time B[s]
-            "3.1".to_string(), // Dropout Delay Time[s]
-            "3.1".to_string(), // Priority Sample Time[s]
+            if scanlist.scan_mode == Some(true) { "On" } else { "Off" }.to_string(), // Scan Mode
+            write_priority_channel_select(scanlist), // Priority Channel Select
+            write_scan_list_channel(&scanlist.priority_channel_1), // Priority Channel 1
+            priority_frequencies[0].0.clone(), // Priority Channel 1 RX Frequency
+            priority_frequencies[0].1.clone(), // Priority Channel 1 TX Frequency
+            write_scan_list_channel(&scanlist.priority_channel_2), // Priority Channel 2
+            priority_frequencies[1].0.clone(), // Priority Channel 2 RX Frequency
+            priority_frequencies[1].1.clone(), // Priority Channel 2 TX Frequency
+            write_scan_revert(scanlist, opt), // Revert Channel
+            format!("{:0.1}", scanlist.look_back_time_a.unwrap_or(2.0)), // Look Back Time A[s]
+            format!("{:0.1}", scanlist.look_back_time_b.unwrap_or(3.0)), // Look Back Time B[s]
+            format!("{:0.1}", scanlist.dropout_delay_time.unwrap_or(3.1)), // Dropout Delay Time[s]
+            format!("{:0.1}", scanlist.dwell_time.unwrap_or(3.1)), // Dwell Time[s]
         ])?;
     }
 
@@ -1113,6 +1824,225 @@ pub fn write_radio_id_list(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Re
     Ok(())
 }
 
+pub fn write_broadcast_memories(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Anytone CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    writer.write_record([
+        "No.",
+        "Frequency[MHz]",
+        "Scan",
+    ])?;
+
+    for (ii, memory) in codeplug.broadcast_memories.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing broadcast memory {:width$}: {}", ii + 1, freq2str(&memory.frequency), width = 3);
+        writer.write_record(&[
+            format!("{}", ii + 1), // No.
+            format!("{:0.3}", (memory.frequency / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Frequency[MHz]
+            if memory.scan { "Add" } else { "Del" }.to_string(), // Scan
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn write_airband_memories(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Anytone CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    writer.write_record([
+        "No.",
+        "Frequency[MHz]",
+        "Scan",
+        "Name",
+    ])?;
+
+    for (ii, memory) in codeplug.airband_memories.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing airband memory {:width$}: {}", ii + 1, freq2str(&memory.frequency), width = 3);
+        writer.write_record(&[
+            format!("{}", ii + 1), // No.
+            format!("{:0.4}", (memory.frequency / Decimal::new(1_000_000, 0)).to_f64().unwrap()), // Frequency[MHz]
+            if memory.scan { "1" } else { "0" }.to_string(), // Scan
+            format!("{:16}", memory.name), // Name
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+// Look up the index of the nearest backlight timeout option that is at least the given number of seconds
+fn write_backlight_timeout(seconds: u32) -> String {
+    match BACKLIGHT_TIMEOUTS.iter().skip(1).position(|&t| t >= seconds) {
+        Some(ii) => (ii + 1).to_string(),
+        None => (BACKLIGHT_TIMEOUTS.len() - 1).to_string(),
+    }
+}
+
+// Auto-repeater offsets selected for VHF and UHF, 1-based indices into AutoRepeaterOffsetFrequencys.CSV
+// (None leaves the setting alone)
+#[derive(Default)]
+pub struct AutoRepeaterIndices {
+    vhf: Option<usize>,
+    uhf: Option<usize>,
+}
+
+// Find the index of the nominal offset of the band containing the given frequency in the offset table
+fn auto_repeater_index(bandplan: &bandplan::Bandplan, offsets: &[Decimal], frequency: Decimal) -> Option<usize> {
+    let offset = bandplan::get_band(bandplan, frequency)?.nominal_offsets.as_ref()?.first()?;
+    offsets.iter().position(|o| o == offset).map(|ii| ii + 1)
+}
+
+// Raw general settings are only written back to the radio they came from
+fn raw_settings_apply(codeplug: &Codeplug) -> bool {
+    codeplug.source == Path::new(file!()).file_stem().unwrap().to_str().unwrap()
+        && codeplug.config.as_ref().and_then(|config| config.general_settings.as_ref()).is_some_and(|settings| settings.raw.is_some())
+}
+
+pub fn write_general_settings(codeplug: &Codeplug, auto_repeater: &AutoRepeaterIndices, path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    // without general settings, the CPS defaults are written (so the auto-repeater offsets are still selected)
+    let default_settings = GeneralSettings::default();
+    let settings = match codeplug.config.as_ref().and_then(|config| config.general_settings.as_ref()) {
+        Some(settings) => settings,
+        None => {
+            uprintln!(opt, Stderr, None, 1, "No general settings, writing the CPS defaults");
+            &default_settings
+        }
+    };
+
+    // start from the CPS defaults, then apply raw settings (only if they came from this radio)
+    let mut values: HashMap<&str, String> = OPTIONAL_SETTING_DEFAULTS.iter()
+        .map(|(key, value)| (*key, value.to_string()))
+        .collect();
+    if let Some(raw) = &settings.raw {
+        if raw_settings_apply(codeplug) {
+            for (key, value) in raw {
+                if let Some(v) = values.get_mut(key.as_str()) {
+                    *v = value.clone();
+                }
+            }
+        } else {
+            uprintln!(opt, Stderr, None, 1, "Ignoring raw general settings from {}", codeplug.source);
+        }
+    }
+
+    // apply the normalized settings, leaving anything unset alone
+    if let Some(intro_line_1) = &settings.intro_line_1 {
+        values.insert("Start_Char", intro_line_1.clone());
+    }
+    if let Some(intro_line_2) = &settings.intro_line_2 {
+        values.insert("Start_Char2", intro_line_2.clone());
+    }
+    if let Some(beep) = settings.beep {
+        values.insert("Beep", if beep { "1" } else { "0" }.to_string());
+    }
+    match settings.tx_tot {
+        Timeout::Default => {},
+        Timeout::Infinite => { values.insert("TOT", "0".to_string()); },
+        Timeout::Seconds(s) => { values.insert("TOT", ((s + 15) / 30).max(1).to_string()); },
+    }
+    if let Some(mic_level) = settings.mic_level {
+        values.insert("MicLevel", ((mic_level.clamp(1, 10) - 1) / 2).to_string());
+    }
+    if let Some(vox_level) = settings.vox_level {
+        values.insert("VOX", vox_level.min(10).div_ceil(3).min(3).to_string());
+    }
+    if let Squelch::Percent(percent) = settings.squelch {
+        let level = ((percent.min(100) as u32 + 10) / 20).to_string();
+        values.insert("SQL1", level.clone());
+        values.insert("SQL2", level);
+    }
+    if let Some(power_save) = settings.power_save {
+        // keep the existing ratio if power save is already on
+        if !power_save {
+            values.insert("PowerSave", "0".to_string());
+        } else if values.get("PowerSave").unwrap() == "0" {
+            values.insert("PowerSave", "1".to_string());
+        }
+    }
+    match settings.backlight_timeout {
+        Timeout::Default => {},
+        Timeout::Infinite => { values.insert("AutoBKLightTime", "0".to_string()); },
+        Timeout::Seconds(s) => { values.insert("AutoBKLightTime", write_backlight_timeout(s)); },
+    }
+    // VFO A and B auto-repeater offsets, 0 is off
+    // raw settings from this radio already index the same offset table, so they are kept
+    if raw_settings_apply(codeplug) {
+        uprintln!(opt, Stderr, None, 1, "Keeping the auto-repeater settings from {}", codeplug.source);
+    } else if let Some(vhf) = auto_repeater.vhf {
+        values.insert("VhfAutoRep", vhf.to_string());
+        values.insert("VhfAutoRep2", vhf.to_string());
+    }
+    if let Some(uhf) = auto_repeater.uhf {
+        values.insert("UhfAutoRep", uhf.to_string());
+        values.insert("UhfAutoRep2", uhf.to_string());
+    }
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Anytone CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    writer.write_record(OPTIONAL_SETTING_DEFAULTS.iter().map(|(key, _)| *key))?;
+    // write the (only) row
+    writer.write_record(OPTIONAL_SETTING_DEFAULTS.iter().map(|(key, _)| values.get(key).unwrap().as_str()))?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_offset_frequency(offset: &Decimal) -> String {
+    if *offset < Decimal::new(1_000_000, 0) {
+        format!("{:0.2} KHz", (offset / Decimal::new(1_000, 0)).to_f64().unwrap())
+    } else {
+        format!("{:0.5} MHz", (offset / Decimal::new(1_000_000, 0)).to_f64().unwrap())
+    }
+}
+
+pub fn write_auto_repeater_offsets(offsets: &[Decimal], path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    uprintln!(opt, Stderr, None, 1, "Writing {}", path.display());
+
+    let mut writer = csv::WriterBuilder::new()
+        .quote_style(csv::QuoteStyle::Always) // Anytone CPS expects all fields to be quoted
+        .terminator(csv::Terminator::CRLF)
+        .from_path(path)?;
+
+    // write the header
+    writer.write_record([
+        "No.",
+        "Offset Frequency",
+    ])?;
+
+    for (ii, offset) in offsets.iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Writing offset {:width$}: {}", ii + 1, freq2str(offset), width = 3);
+        writer.write_record(&[
+            format!("{}", ii + 1), // No.
+            write_offset_frequency(offset), // Offset Frequency
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
 pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<(), Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     uprintln!(opt, Stderr, None, 4, "props = {:?}", get_props());
@@ -1151,6 +2081,13 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
         write_talkgroup_lists(codeplug, &talkgroup_lists_path, opt)?;
     }
 
+    // write to DigitalContactList.CSV
+    let mut contacts_path: PathBuf = output_path.clone();
+    contacts_path.push("DigitalContactList.CSV");
+    if !codeplug.contacts.is_empty() {
+        write_contacts(codeplug, &contacts_path, opt)?;
+    }
+
     // write to Channel.CSV
     let mut channels_path: PathBuf = output_path.clone();
     channels_path.push("Channel.CSV");
@@ -1181,5 +2118,44 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
         }
     }
 
+    // write to AutoRepeaterOffsetFrequencys.CSV
+    // use the offsets from the source codeplug if present (or if its raw settings, which index the table, are
+    // written back), otherwise fill from the bandplan, and point the VHF/UHF auto-repeater settings at the
+    // 2m/70cm offsets in the table
+    let mut offsets_path: PathBuf = output_path.clone();
+    offsets_path.push("AutoRepeaterOffsetFrequencys.CSV");
+    let bandplan = bandplan::load_bandplan(opt)?;
+    let offsets = match codeplug.config.as_ref().and_then(|config| config.auto_repeater_offsets.clone()) {
+        Some(offsets) if !offsets.is_empty() || raw_settings_apply(codeplug) => offsets,
+        _ => bandplan::get_repeater_offsets(&bandplan),
+    };
+    let auto_repeater = AutoRepeaterIndices {
+        vhf: auto_repeater_index(&bandplan, &offsets, Decimal::from(146_000_000)),
+        uhf: auto_repeater_index(&bandplan, &offsets, Decimal::from(440_000_000)),
+    };
+    if !raw_settings_apply(codeplug) && (auto_repeater.vhf.is_none() || auto_repeater.uhf.is_none()) {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Bandplan offsets for 2m/70cm are not in the auto-repeater offset table, auto-offset is not configured for that band");
+    }
+    write_auto_repeater_offsets(&offsets, &offsets_path, opt)?;
+
+    // write to FM.CSV
+    let mut broadcast_path: PathBuf = output_path.clone();
+    broadcast_path.push("FM.CSV");
+    if !codeplug.broadcast_memories.is_empty() {
+        write_broadcast_memories(codeplug, &broadcast_path, opt)?;
+    }
+
+    // write to AMAir.CSV
+    let mut airband_path: PathBuf = output_path.clone();
+    airband_path.push("AMAir.CSV");
+    if !codeplug.airband_memories.is_empty() {
+        write_airband_memories(codeplug, &airband_path, opt)?;
+    }
+
+    // write to OptionalSetting.CSV
+    let mut optional_setting_path: PathBuf = output_path.clone();
+    optional_setting_path.push("OptionalSetting.CSV");
+    write_general_settings(codeplug, &auto_repeater, &optional_setting_path, opt)?;
+
     Ok(())
 }
@@ -6,6 +6,7 @@
 use std::path::PathBuf;
 use std::path::Path;
 use std::collections::HashMap;
+use std::collections::BTreeSet;
 use rust_decimal::prelude::*;
 use std::sync::OnceLock;
 use std::cmp::{max, min};
@@ -22,6 +23,13 @@ pub fn get_props() -> &'static structures::RadioProperties {
         props.channel_name_width_max = 16;
         props.zones_max = 0; // chirp doesn't support zones
         props.zone_name_width_max = 0;
+        props.scanlist_name_width_max = 0;
+        props.talkgroup_name_width_max = 0;
+        props.talkgroup_list_name_width_max = 0;
+        props.contacts_max = 0;
+        props.mixed_mode = false;
+        props.ctcss_custom = false;
+        props.dcs_custom = false;
         // dynamically set
         props.channel_index_width = (props.channels_max as f64).log10().ceil() as usize;
         props.zone_index_width = (props.zones_max as f64).log10().ceil() as usize;
@@ -29,6 +37,11 @@ pub fn get_props() -> &'static structures::RadioProperties {
     })
 }
 
+// Radio model name, used to key vendor extensions
+fn model() -> &'static str {
+    Path::new(file!()).file_stem().unwrap().to_str().unwrap()
+}
+
 // CSV Export Format
 // CHIRP next-20241108
 // CHIRP exports a single CSV file:
@@ -48,7 +61,7 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - DtcsPolarity: DCS polarity, NN default
 // - RxDtcsCode: RX DCS code, 23 default
 // - CrossMode: [Tone->Tone,Tone->DTCS,DTCS->Tone,->Tone,->DTCS,DTCS->,DTCS->DTCS]
-// - Mode: [FM, NFM, ??]
+// - Mode: [FM, NFM, WFM, AM, ??]
 // - TStep: default 5
 // - Skip: [(blank), ??]
 // - Power: power in watts with W suffix, e.g. [1.0W, 4.0W, 50W]
@@ -145,16 +158,25 @@ fn parse_tones(record: &CsvRecord) -> Result<(Option<Tone>, Option<Tone>), Box<d
     }
 }
 
+// Columns in the Chirp CSV that are mapped into Channel (not passed through extensions)
+const CHANNEL_MAPPED: [&str; 15] = [
+    "Location", "Name", "Frequency", "Duplex", "Offset", "Tone", "rToneFreq", "cToneFreq", "DtcsCode",
+    "DtcsPolarity", "RxDtcsCode", "CrossMode", "Mode", "Skip", "Power",
+];
+
 pub fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
 
     let mut channel = Channel::default();
 
     // chirp uses zero-index, +1 to match other CPS
-    if record.get("Mode").unwrap().as_str() == "NFM" || record.get("Mode").unwrap().as_str() == "FM" {
+    if matches!(record.get("Mode").unwrap().as_str(), "NFM" | "FM" | "WFM" | "AM") {
         channel.index = record.get("Location").unwrap().parse::<usize>()? + 1;
         channel.name = record.get("Name").unwrap().to_string();
-        channel.mode = ChannelMode::FM;
+        channel.mode = match record.get("Mode").unwrap().as_str() {
+            "AM" => ChannelMode::AM,
+            _ => ChannelMode::FM,
+        };
         channel.frequency_rx = Decimal::from_str(record.get("Frequency").unwrap())? * Decimal::new(1_000_000, 0);
         let offset = match record.get("Offset").unwrap().as_str() {
             "" => Decimal::new(0, 0),
@@ -179,6 +201,11 @@ pub fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Bo
             _ => false,
             }
         }));
+        channel.extensions = super::get_extensions(record, &CHANNEL_MAPPED, model());
+        // AM channels have no mode-specific properties
+        if channel.mode == ChannelMode::AM {
+            return Ok(channel);
+        }
         // FM specific properties
         let (tone_tx, tone_rx) = match parse_tones(record) {
             Ok((rx, tx)) => (rx, tx),
@@ -188,6 +215,7 @@ pub fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Bo
             bandwidth: match record.get("Mode").unwrap().as_str() {
                 "FM" => Decimal::new(25_000, 0),
                 "NFM" => Decimal::new(12_500, 0),
+                "WFM" => Decimal::new(200_000, 0),
                 _ => return Err(format!("Unsupported mode: {}", record.get("mode").unwrap()).into()),
             },
             squelch: Squelch::Default, // chirp doesn't support squelch
@@ -530,8 +558,12 @@ fn write_tones(channel: &Channel) -> (String, String, String, String, String, St
 }
 
 fn write_mode(channel: &Channel) -> Result<String, Box<dyn Error>> {
+    if channel.mode == ChannelMode::AM {
+        return Ok("AM".to_string());
+    }
     let bandwidth = channel.fm.as_ref().unwrap().bandwidth;
     match bandwidth.to_u32().unwrap() {
+        200_000 => Ok("WFM".to_string()),
         25_000 => Ok("FM".to_string()),
         12_500 => Ok("NFM".to_string()),
         _ => Err("Unsupported bandwidth".into()),
@@ -556,7 +588,7 @@ fn write_channels(codeplug: &Codeplug, path: &Path, opt: &Opt) -> Result<(), Box
         .from_path(path)?;
 
     // write the header
-    writer.write_record(&[
+    let header = [
         "Location",
         "Name",
         "Frequency",
@@ -578,16 +610,24 @@ fn write_channels(codeplug: &Codeplug, path: &Path, opt: &Opt) -> Result<(), Box
         "RPT1CALL",
         "RPT2CALL",
         "DVCODE",
-    ])?;
+    ];
+    writer.write_record(header)?;
+
+    // chirp has no broadcast/airband lists, so these are written as RX-only channels after the regular channels
+    let memory_channels = super::receive_memories_to_channels(codeplug);
+    if !memory_channels.is_empty() {
+        uprintln!(opt, Stderr, None, 1, "Writing {} broadcast/airband memories as channels", memory_channels.len());
+    }
 
-    for channel in &codeplug.channels {
+    let mut dropped = BTreeSet::new();
+    for channel in codeplug.channels.iter().chain(memory_channels.iter()) {
         uprintln!(opt, Stderr, None, 4, "Writing channel {:width$}: {}", channel.index, channel.name, width=get_props().channel_index_width);
-        if channel.mode == ChannelMode::FM {
+        if channel.mode == ChannelMode::FM || channel.mode == ChannelMode::AM {
             let (frequency, duplex, offset) = write_frequencies(channel);
             let (tone, r_tone_freq, c_tone_freq, dtcs_code, dtcs_polarity, rx_dtcs_code, cross_mode) = write_tones(channel);
             uprintln!(opt, Stderr, Color::Red, 4, "    tone: {}, rToneFreq: {}, cToneFreq: {}, DtcsCode: {}, DtcsPolarity: {}, RxDtcsCode: {}, CrossMode: {}",
                 tone, r_tone_freq, c_tone_freq, dtcs_code, dtcs_polarity, rx_dtcs_code, cross_mode);
-            writer.write_record(&[
+            let mut record = [
                 (channel.index - 1).to_string(), // Location (zero-indexed)
                 channel.name.clone(), // Name
                 frequency, // Frequency
@@ -612,13 +652,16 @@ fn write_channels(codeplug: &Codeplug, path: &Path, opt: &Opt) -> Result<(), Box
                 "".to_string(), // RPT1CALL
                 "".to_string(), // RPT2CALL
                 "".to_string(), // DVCODE
-            ])?;
+            ];
+            super::apply_extensions(&header, &mut record, &CHANNEL_MAPPED, &channel.extensions, model(), &mut dropped);
+            writer.write_record(&record)?;
         } else {
             uprintln!(opt, Stderr, Color::Red, None, "Unsupported mode: index = {}, mode = {:?}", channel.index, channel.mode);
         }
     }
 
     writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
     Ok(())
 }
 
@@ -2,9 +2,13 @@
 
 use std::error::Error;
 use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use rust_decimal::Decimal;
 
 use crate::Opt;
 use crate::structures::Codeplug;
+use crate::structures::*;
 use crate::*;
 
 mod anytone_x78;
@@ -28,7 +32,10 @@ pub fn parse_codeplug(opt: &Opt, model: &String, input: &PathBuf) -> Result<Code
 
     // look up the radio model in the hashmap
     if let Some(read_function) = read_functions.get(model.as_str()) {
-        return read_function(input, opt);
+        // radios reference entities by name, convert to IDs
+        let mut codeplug = read_function(input, opt)?;
+        crate::ids::names_to_ids(&mut codeplug);
+        return Ok(codeplug);
     } else {
         uprintln!(opt, Stderr, Color::Red, None, "Unsupported radio model for operation: parse");
         uprintln!(opt, Stderr, None, None, "Operation \"parse\" supports the following radio models:");
@@ -53,7 +60,8 @@ pub fn generate_codeplug(opt: &Opt, codeplug: &Codeplug, model: &String, output:
 
     // look up the radio model in the hashmap
     if let Some(write_function) = write_functions.get(model.as_str()) {
-        return write_function(codeplug, output, opt);
+        // radios reference entities by name, convert from IDs
+        return write_function(&crate::ids::ids_to_names(codeplug), output, opt);
     } else {
         uprintln!(opt, Stderr, Color::Red, None, "Unsupported radio model for operation: write");
         uprintln!(opt, Stderr, None, None, "Operation \"write\" supports the following radio models:");
@@ -64,8 +72,7 @@ pub fn generate_codeplug(opt: &Opt, codeplug: &Codeplug, model: &String, output:
     }
 }
 
-pub fn get_properties(opt: &Opt, model: &String) -> Result<structures::RadioProperties, Box<dyn Error>> {
-    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+fn properties_functions() -> HashMap<&'static str, fn() -> &'static structures::RadioProperties> {
     // build up a hashmap of function pointers
     let mut properties_functions: HashMap<&str, fn() -> &'static structures::RadioProperties>
         = HashMap::new();
@@ -75,6 +82,12 @@ pub fn get_properties(opt: &Opt, model: &String) -> Result<structures::RadioProp
     properties_functions.insert("ailunce_hd1", ailunce_hd1::get_props);
     properties_functions.insert("alinco_djmd5t", alinco_djmd5t::get_props);
     properties_functions.insert("tyt_mduv390", tyt_mduv390::get_props);
+    properties_functions
+}
+
+pub fn get_properties(opt: &Opt, model: &String) -> Result<structures::RadioProperties, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let properties_functions = properties_functions();
 
     // look up the radio model in the hashmap
     if let Some(properties_function) = properties_functions.get(model.as_str()) {
@@ -88,3 +101,91 @@ pub fn get_properties(opt: &Opt, model: &String) -> Result<structures::RadioProp
         return Err("Bad radio model".into());
     }
 }
+
+// Like get_properties, but quietly returns None for unsupported models (e.g. a codeplug source that is not a radio)
+pub fn find_properties(model: &str) -> Option<structures::RadioProperties> {
+    properties_functions().get(model).map(|properties_function| properties_function().clone())
+}
+
+// Map broadcast FM and airband AM memories onto RX-only channels, for radios that don't have dedicated lists
+// the new channels are indexed after the last channel in the codeplug
+pub fn receive_memories_to_channels(codeplug: &Codeplug) -> Vec<Channel> {
+    let mut channels: Vec<Channel> = Vec::new();
+    let mut index = codeplug.channels.iter().map(|c| c.index).max().unwrap_or(0);
+    for (prefix, memories) in [("FM", &codeplug.broadcast_memories), ("AIR", &codeplug.airband_memories)] {
+        for memory in memories {
+            index += 1;
+            let mut channel = Channel {
+                index,
+                name: if memory.name.is_empty() {
+                    format!("{} {}", prefix, (memory.frequency / Decimal::new(1_000_000, 0)).normalize())
+                } else {
+                    memory.name.clone()
+                },
+                frequency_rx: memory.frequency,
+                frequency_tx: memory.frequency,
+                rx_only: true,
+                scan: Some(Scan::Skip(ScanSkip { zone: false, all: !memory.scan })),
+                ..Default::default()
+            };
+            if prefix == "FM" {
+                // broadcast FM is wideband FM
+                channel.mode = ChannelMode::FM;
+                channel.fm = Some(FmChannel {
+                    bandwidth: Decimal::new(200_000, 0),
+                    squelch: Squelch::Default,
+                    tone_rx: None,
+                    tone_tx: None,
+                });
+            } else {
+                channel.mode = ChannelMode::AM;
+            }
+            channels.push(channel);
+        }
+    }
+    channels
+}
+
+// Collect the columns of a CSV record that are not mapped into the intermediary format,
+// returned as extensions for this model (None if every column is mapped)
+pub fn get_extensions(record: &HashMap<String, String>, mapped: &[&str], model: &str) -> Option<Extensions> {
+    let columns: BTreeMap<String, String> = record.iter()
+        .filter(|(key, _)| !mapped.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    if columns.is_empty() {
+        return None;
+    }
+    Some(Extensions::from([(model.to_string(), columns)]))
+}
+
+// Overwrite the unmapped columns of a CSV record with the extensions for this model,
+// columns without an extension (or extensions from another model) keep the written default.
+// Extension columns that are not in the header (e.g. from another CPS version) can't be written,
+// their names are added to dropped so the caller can warn once per file
+pub fn apply_extensions(header: &[&str], record: &mut [String], mapped: &[&str], extensions: &Option<Extensions>, model: &str, dropped: &mut BTreeSet<String>) {
+    if let Some(columns) = extensions.as_ref().and_then(|x| x.get(model)) {
+        for (ii, key) in header.iter().enumerate() {
+            if mapped.contains(key) {
+                continue;
+            }
+            if let Some(value) = columns.get(*key) {
+                record[ii] = value.clone();
+            }
+        }
+        for key in columns.keys() {
+            if !header.contains(&key.as_str()) && !mapped.contains(&key.as_str()) {
+                dropped.insert(key.clone());
+            }
+        }
+    }
+}
+
+// Warn about extension columns that apply_extensions() could not write
+pub fn warn_dropped_extensions(path: &std::path::Path, dropped: &BTreeSet<String>, opt: &Opt) {
+    if !dropped.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Columns not supported in {}, dropping: {}",
+            path.file_name().unwrap_or_default().to_string_lossy(),
+            dropped.iter().map(|x| x.as_str()).collect::<Vec<&str>>().join(", "));
+    }
+}
@@ -6,6 +6,7 @@
 use std::path::PathBuf;
 use std::path::Path;
 use std::collections::HashMap;
+use std::collections::BTreeSet;
 use rust_decimal::prelude::*;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -22,6 +23,13 @@ pub fn get_props() -> &'static structures::RadioProperties {
         props.channel_name_width_max = 16;
         props.zones_max = 68;
         props.zone_name_width_max = 16;
+        props.scanlist_name_width_max = 0; // scan lists are not written
+        props.talkgroup_name_width_max = 16;
+        props.talkgroup_list_name_width_max = 16;
+        props.contacts_max = 1024;
+        props.mixed_mode = false;
+        props.ctcss_custom = false;
+        props.dcs_custom = false;
         // dynamically set
         props.channel_index_width = (props.channels_max as f64).log10().ceil() as usize;
         props.zone_index_width = (props.zones_max as f64).log10().ceil() as usize;
@@ -29,6 +37,11 @@ pub fn get_props() -> &'static structures::RadioProperties {
     })
 }
 
+// Radio model name, used to key vendor extensions
+fn model() -> &'static str {
+    Path::new(file!()).file_stem().unwrap().to_str().unwrap()
+}
+
 // CSV Export Format
 // OpenGD77 CPS Version R2024.09.13.02
 /* Files
@@ -52,8 +65,8 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Contact: talkgroup name, blank for Analogue, None for when TG List below is set
 // - TG List: talkgroup list name, blank for Analogue, None for when Contact above is set
 // - DMR ID: None
-// - TS1_TA_Tx: timeslot 1 talker alias, [Off, ???] @TODO
-// - TS2_TA_Tx ID: timeslot 2 talker alias, [Off, ???] @TODO
+// - TS1_TA_Tx: timeslot 1 talker alias, [Off, APRS, Text, APRS+Text], blank for Analogue
+// - TS2_TA_Tx ID: timeslot 2 talker alias, [Off, APRS, Text, APRS+Text], blank for Analogue
 // - RX Tone: None, CTCSS frequency in Hz, or DCS code (DnnnN or DnnnI), blank for Digital
 // - TX Tone: None, CTCSS frequency in Hz, or DCS code (DnnnN or DnnnI), blank for Digital
 // - Squelch: blank for Digital, [Disabled,Open,Closed,5%..95%] (default is Disabled)
@@ -75,7 +88,9 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Contact Name: talkgroup name
 // - ID: talkgroup ID
 // - ID Type: [Group,Private,AllCall]
-// - TS Override: [Disabled, 1, 2]
+// - TS Override: [Disabled, 1, 2], transmit on this timeslot regardless of the channel
+// Talkgroups and private contacts share this file (and the 1024 entry limit), so Private entries
+// not referenced by any channel or TG list are treated as DMR contacts rather than talkgroups
 
 // TG_Lists.csv
 // - TG List Name: talkgroup list name
@@ -89,11 +104,21 @@ pub fn get_props() -> &'static structures::RadioProperties {
 
 // READ ///////////////////////////////////////////////////////////////////////
 
+// Convert a TS Override string into a timeslot
+fn parse_timeslot_override(ts_override: &str) -> Option<u8> {
+    match ts_override {
+        "1" => Some(1),
+        "2" => Some(2),
+        _ => None, // Disabled
+    }
+}
+
 pub fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
     static TALKGROUP_INDEX: AtomicUsize = AtomicUsize::new(1);
     let talkgroup = DmrTalkgroup {
         index: TALKGROUP_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         id: record.get("ID").unwrap().parse()?,
         name: record.get("Contact Name").unwrap().to_string(),
         call_type: match record.get("ID Type").unwrap().as_str() {
@@ -103,6 +128,8 @@ pub fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgr
             _ => return Err(format!("Unrecognized call type: {}", record.get("Call Type").unwrap()).into()),
         },
         alert: false, // not supported
+        timeslot_override: parse_timeslot_override(record.get("TS Override").unwrap()),
+        extensions: None, // all columns are mapped
     };
     Ok(talkgroup)
 }
@@ -112,8 +139,10 @@ pub fn parse_talkgroup_list_record(record: &CsvRecord, codeplug: &Codeplug, opt:
     static TALKGROUP_LIST_INDEX: AtomicUsize = AtomicUsize::new(1);
     let mut talkgroup_list = DmrTalkgroupList {
         index: TALKGROUP_LIST_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         name: record.get("TG List Name").unwrap().to_string(),
         talkgroups: Vec::new(),
+        extensions: None, // all columns are mapped
     };
     // iterate over the contacts in the CSV record, up to 32 (do not use for (k,v) in .. because it doesn't care about order)
     for ii in 1..=32 {
@@ -170,6 +199,17 @@ fn parse_tone(tone: &str) -> Option<Tone> {
     return Some(Tone::Ctcss(tone.trim().parse::<f64>().unwrap()));
 }
 
+// Convert a talker alias string into a TalkerAlias struct
+fn parse_talker_alias(talker_alias: &str) -> Option<TalkerAlias> {
+    match talker_alias {
+        "Off" => Some(TalkerAlias::Off),
+        "APRS" => Some(TalkerAlias::Aprs),
+        "Text" => Some(TalkerAlias::Text),
+        "APRS+Text" => Some(TalkerAlias::AprsText),
+        _ => None,
+    }
+}
+
 // Convert a squelch string into a Squelch struct
 // OpenGD77 stores squelch as follows:
 // - "Disabled" for default squelch (set by menu)
@@ -187,6 +227,13 @@ fn parse_squelch(squelch: &str) -> Squelch {
     }
 }
 
+// Columns in Channels.csv that are mapped into Channel (not passed through extensions)
+const CHANNEL_MAPPED: [&str; 20] = [
+    "Channel Number", "Channel Name", "Channel Type", "Rx Frequency", "Tx Frequency", "Bandwidth (kHz)",
+    "Colour Code", "Timeslot", "Contact", "TG List", "TS1_TA_Tx", "TS2_TA_Tx ID", "RX Tone", "TX Tone",
+    "Squelch", "Power", "Rx Only", "Zone Skip", "All Skip", "TOT",
+];
+
 pub fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
     let mut channel = Channel::default();
@@ -238,8 +285,16 @@ pub fn parse_channel_record(record: &CsvRecord, opt: &Opt) -> Result<Channel, Bo
                 Some(record.get("TG List").unwrap().to_string())
             },
             id_name: None,
+            talker_alias_ts1: parse_talker_alias(record.get("TS1_TA_Tx").unwrap()),
+            talker_alias_ts2: parse_talker_alias(record.get("TS2_TA_Tx ID").unwrap()),
+            simplex_tdma: None, // not supported
+            tdma_adaptive: None, // not supported
+            dcdm_leader: None, // not supported
+            talkaround: None, // not supported
+            reverse: None, // not supported
         });
     }
+    channel.extensions = super::get_extensions(record, &CHANNEL_MAPPED, model());
     Ok(channel)
 }
 
@@ -248,8 +303,13 @@ pub fn parse_zone_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Opt) ->
     static ZONE_INDEX: AtomicUsize = AtomicUsize::new(1);
     let mut zone = Zone {
         index: ZONE_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         name: record.get("Zone Name").unwrap().to_string(),
         channels: Vec::new(),
+        a_channel: None, // not supported
+        b_channel: None, // not supported
+        hidden: None, // not supported
+        extensions: None, // all columns are mapped
     };
     // iterate over the channels in the CSV record, up to 80 (do not use for (k,v) in .. because it doesn't care about order)
     for ii in 1..=80 {
@@ -346,6 +406,23 @@ pub fn read(input_path: &PathBuf, opt: &Opt) -> Result<Codeplug, Box<dyn Error>>
             codeplug.zones.push(zone);
         }
     }
+    // move unreferenced Private talkgroups into codeplug.contacts
+    let (contacts, talkgroups): (Vec<DmrTalkgroup>, Vec<DmrTalkgroup>) = codeplug.talkgroups.drain(..).partition(|tg| {
+        tg.call_type == DmrTalkgroupCallType::Private
+            && !codeplug.channels.iter().any(|ch| ch.dmr.as_ref().is_some_and(|dmr| dmr.talkgroup.as_ref() == Some(&tg.name)))
+            && !codeplug.talkgroup_lists.iter().any(|tgl| tgl.talkgroups.iter().any(|t| t.name == tg.name))
+    });
+    codeplug.talkgroups = talkgroups;
+    for (ii, tg) in contacts.into_iter().enumerate() {
+        uprintln!(opt, Stderr, None, 4, "Private contact: {}", tg.name);
+        codeplug.contacts.push(DmrContact {
+            index: ii + 1,
+            id: tg.id,
+            callsign: tg.name,
+            ..Default::default()
+        });
+    }
+
     Ok(codeplug)
 }
 
@@ -377,6 +454,20 @@ pub fn write_talkgroups(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Resul
                 DmrTalkgroupCallType::Private => "Private",
                 DmrTalkgroupCallType::AllCall => "AllCall",
             }.to_string(), // ID Type
+            match talkgroup.timeslot_override {
+                Some(ts) => ts.to_string(),
+                None => "Disabled".to_string(),
+            }, // TS Override
+        ])?;
+    }
+
+    // contacts go in the same file as private calls
+    for contact in &codeplug.contacts {
+        uprintln!(opt, Stderr, None, 4, "Writing contact: {}", contact.callsign);
+        writer.write_record(&[
+            contact.callsign.clone(), // Contact Name
+            contact.id.to_string(), // ID
+            "Private".to_string(), // ID Type
             "Disabled".to_string(), // TS Override
         ])?;
     }
@@ -431,6 +522,15 @@ fn write_tone(tone: &Option<Tone>) -> String {
     }
 }
 
+fn write_talker_alias(talker_alias: &Option<TalkerAlias>) -> String {
+    match talker_alias {
+        Some(TalkerAlias::Off) | None => "Off",
+        Some(TalkerAlias::Aprs) => "APRS",
+        Some(TalkerAlias::Text) => "Text",
+        Some(TalkerAlias::AprsText) => "APRS+Text",
+    }.to_string()
+}
+
 fn write_squelch(squelch: &Squelch) -> String {
     match squelch { // 0 is Open, 100 is Closed
         Squelch::Default => "Disabled".to_string(),
@@ -486,7 +586,7 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
         .from_path(path)?;
 
     // write the header
-    writer.write_record(&[
+    let header = [
         "Channel Number",
         "Channel Name",
         "Channel Type",
@@ -515,12 +615,14 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
         "Latitude",
         "Longitude",
         "Use location",
-    ])?;
+    ];
+    writer.write_record(header)?;
 
+    let mut dropped = BTreeSet::new();
     for channel in &codeplug.channels {
         uprintln!(opt, Stderr, None, 4, "Writing channel {:width$}: {}", channel.index, channel.name, width = get_props().channel_index_width);
-        if channel.mode == ChannelMode::FM {
-            writer.write_record(&[
+        let mut record = if channel.mode == ChannelMode::FM {
+            [
                 channel.index.to_string(), // Channel Number
                 channel.name.clone(), // Channel Name
                 "Analogue".to_string(), // Channel Type
@@ -550,9 +652,9 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 "0".to_string(), // Latitude
                 "0".to_string(), // Longitude
                 "No".to_string(), // Use Location
-            ])?;
+            ]
         } else if channel.mode == ChannelMode::DMR {
-            writer.write_record(&[
+            [
                 channel.index.to_string(), // Channel Number
                 channel.name.clone(), // Channel Name
                 "Digital".to_string(), // Channel Type
@@ -573,8 +675,8 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                     "None".to_string()
                 }, // TG List
                 "None".to_string(), // DMR ID
-                "Off".to_string(), // TS1_TA_Tx
-                "Off".to_string(), // TS2_TA_Tx ID
+                write_talker_alias(&channel.dmr.as_ref().unwrap().talker_alias_ts1), // TS1_TA_Tx
+                write_talker_alias(&channel.dmr.as_ref().unwrap().talker_alias_ts2), // TS2_TA_Tx ID
                 "".to_string(), // Rx Tone
                 "".to_string(), // Tx Tone
                 "".to_string(), // Squelch
@@ -590,13 +692,17 @@ pub fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<
                 "0".to_string(), // Latitude
                 "0".to_string(), // Longitude
                 "No".to_string(), // Use location
-            ])?;
+            ]
         } else {
             uprintln!(opt, Stderr, Color::Yellow, None, "Unsupported channel mode: index = {}, mode = {:?}", channel.index, channel.mode);
-        }
+            continue;
+        };
+        super::apply_extensions(&header, &mut record, &CHANNEL_MAPPED, &channel.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
     }
 
     writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
 
     Ok(())
 }
@@ -659,7 +765,7 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
     // write to Contacts.csv
     let mut talkgroups_path: PathBuf = output_path.clone();
     talkgroups_path.push("Contacts.csv");
-    if codeplug.talkgroups.len() > 0 {
+    if codeplug.talkgroups.len() > 0 || !codeplug.contacts.is_empty() {
         write_talkgroups(&codeplug, &talkgroups_path, opt)?;
     }
 
@@ -682,5 +788,12 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
         write_zones(&codeplug, &zones_path, opt)?;
     }
 
+    if !codeplug.broadcast_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Broadcast memories are not supported, skipping {} memories", codeplug.broadcast_memories.len());
+    }
+    if !codeplug.airband_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Airband memories are not supported, skipping {} memories", codeplug.airband_memories.len());
+    }
+
     Ok(())
 }
@@ -5,6 +5,7 @@
 use std::path::PathBuf;
 use std::path::Path;
 use std::collections::HashMap;
+use std::collections::BTreeSet;
 use rust_decimal::prelude::*;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -21,6 +22,13 @@ pub fn get_props() -> &'static structures::RadioProperties {
         props.channel_name_width_max = 16;
         props.zones_max = 250;
         props.zone_name_width_max = 16;
+        props.scanlist_name_width_max = 16;
+        props.talkgroup_name_width_max = 16;
+        props.talkgroup_list_name_width_max = 16;
+        props.contacts_max = 0;
+        props.mixed_mode = false;
+        props.ctcss_custom = false;
+        props.dcs_custom = false;
         // dynamically set
         props.channel_index_width = (props.channels_max as f64).log10().ceil() as usize;
         props.zone_index_width = (props.zones_max as f64).log10().ceil() as usize;
@@ -28,6 +36,11 @@ pub fn get_props() -> &'static structures::RadioProperties {
     })
 }
 
+// Radio model name, used to key vendor extensions
+fn model() -> &'static str {
+    Path::new(file!()).file_stem().unwrap().to_str().unwrap()
+}
+
 // CSV Export Format
 // TYT MD-UV380 CPS V2.41
 /* Files
@@ -42,6 +55,8 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - TX Frequency(MHz): unpadded
 // - Band Width: 2 for 25kHz, 1 for 20kHz, 0 for 12.5kHz
 // - Scan List: 0=None, else one-index
+//   - scan lists themselves (including priority channels, designated TX channel, and timing) are not exported by the CPS,
+//     so this is the index into codeplug.scanlists, and the lists must be recreated in the same order in the CPS
 // - Squelch: [0-9], default 1
 // - RX Ref Frequency: default 0
 // - TX Ref Frequency: default 0
@@ -60,8 +75,8 @@ pub fn get_props() -> &'static structures::RadioProperties {
 // - Emergency Alarm Ack: default 0
 // - Data Call Confirmed: default 0
 // - Allow Interrupt: default 0
-// - DCDCM Switch: default 0
-// - Leader/MS: default 1
+// - DCDM Switch: dual capacity direct mode, [0,1] = [off, on], default 0
+// - Leader/MS: DCDM timing role, [0,1] = [Leader, MS], default 1
 // - Emergency System: default 0
 // - Contact Name: 0 for analog channels, index for DMR channels
 // - Group List: 0 for analog channels, index for DMR channels
@@ -96,6 +111,7 @@ fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup,
     static TALKGROUP_INDEX: AtomicUsize = AtomicUsize::new(1);
     let talkgroup = DmrTalkgroup {
         index: TALKGROUP_INDEX.fetch_add(1, Ordering::Relaxed),
+        uid: String::new(),
         id: record.get("Call ID").unwrap().parse::<u32>()?,
         name: record.get("Contact Name").unwrap().to_string(),
         call_type: match record.get("Call Type").unwrap().as_str() {
@@ -105,6 +121,8 @@ fn parse_talkgroup_record(record: &CsvRecord, opt: &Opt) -> Result<DmrTalkgroup,
             _ => return Err(format!("Unrecognized call type: {}", record.get("Call Type").unwrap()).into()),
         },
         alert: record.get("Call Receive Tone").unwrap() == "1",
+        timeslot_override: None, // not supported
+        extensions: None, // all columns are mapped
     };
     Ok(talkgroup)
 }
@@ -134,6 +152,34 @@ fn get_talkgroup_by_index(index: u32, codeplug: &Codeplug) -> Option<String> {
     Some(codeplug.talkgroups[index as usize - 1].name.clone())
 }
 
+// Columns in channels.csv that are mapped into Channel (not passed through extensions),
+// Group List is never mapped since the CPS does not export talkgroup lists
+const CHANNEL_MAPPED: [&str; 8] = [
+    "Channel Mode", "Channel Name", "RX Frequency(MHz)", "TX Frequency(MHz)", "TOT[s]", "Power", "Admit Criteria",
+    "Rx Only",
+];
+// Columns only mapped for channels of one mode, the writer fills in defaults for the other mode
+const CHANNEL_MAPPED_FM: [&str; 4] = ["Band Width", "Squelch", "CTCSS/DCS Dec", "CTCSS/DCS Enc"];
+const CHANNEL_MAPPED_DMR: [&str; 6] = [
+    "Allow Talkaround", "DCDM Switch", "Leader/MS", "Contact Name", "Color Code", "Repeater Slot",
+];
+
+// Columns mapped for this channel, Scan List is only mapped if the channel has a scan list
+// (the CPS doesn't export scan lists, so the reader never sets one)
+fn channel_mapped(channel: &Channel) -> Vec<&'static str> {
+    let mut mapped = CHANNEL_MAPPED.to_vec();
+    if channel.fm.is_some() {
+        mapped.extend(CHANNEL_MAPPED_FM);
+    }
+    if channel.dmr.is_some() {
+        mapped.extend(CHANNEL_MAPPED_DMR);
+    }
+    if let Some(Scan::ScanList(_)) = channel.scan {
+        mapped.push("Scan List");
+    }
+    mapped
+}
+
 fn parse_channel_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> Result<Channel, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 4, "    {:?}", record);
     let mut channel = Channel::default();
@@ -194,10 +240,18 @@ fn parse_channel_record(record: &CsvRecord, codeplug: &Codeplug, opt: &Opt) -> R
                 ),
                 talkgroup_list: None, // CPS does not export talkgroup lists
                 id_name: None,
+                talker_alias_ts1: None, // not supported
+                talker_alias_ts2: None, // not supported
+                simplex_tdma: record.get("DCDM Switch").map(|x| x == "1"),
+                tdma_adaptive: None, // not supported
+                dcdm_leader: record.get("Leader/MS").map(|x| x == "0"),
+                talkaround: record.get("Allow Talkaround").map(|x| x == "1"),
+                reverse: None, // not supported
             });
         }
         _ => {}
     }
+    channel.extensions = super::get_extensions(record, &channel_mapped(&channel), model());
 
     Ok(channel)
 }
@@ -355,6 +409,19 @@ fn write_group_list(_channel: &Channel, _codeplug: &Codeplug) -> String {
     "0".to_string()
 }
 
+fn write_scan_list(channel: &Channel, codeplug: &Codeplug) -> String {
+    match &channel.scan {
+        Some(Scan::ScanList(name)) => {
+            // find the scan list in the codeplug
+            match codeplug.scanlists.iter().position(|x| x.name == *name) {
+                Some(index) => (index + 1).to_string(),
+                None => "0".to_string(),
+            }
+        },
+        _ => "0".to_string(),
+    }
+}
+
 fn write_power(power: &Power) -> String {
     match power {
         Power::Default => "2".to_string(), // Default to High
@@ -374,7 +441,7 @@ fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
         .from_path(path)?;
 
     // write the header
-    writer.write_record(&[
+    let header = [
         "Channel Mode",
         "Channel Name",
         "RX Frequency(MHz)",
@@ -426,12 +493,14 @@ fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
         "Decode 6",
         "Decode 7",
         "Decode 8",
-    ])?;
+    ];
+    writer.write_record(header)?;
 
+    let mut dropped = BTreeSet::new();
     for channel in &codeplug.channels {
         uprintln!(opt, Stderr, None, 4, "Writing channel {:width$}: {}", channel.index, channel.name, width = get_props().channel_index_width);
-        if channel.mode == ChannelMode::FM {
-            writer.write_record(&[
+        let mut record = if channel.mode == ChannelMode::FM {
+            [
                 "1".to_string(), // Channel Mode
                 channel.name.clone(), // Channel Name
                 format!("{:.5}", channel.frequency_rx / Decimal::new(1_000_000, 0)), // RX Frequency(MHz)
@@ -442,7 +511,7 @@ fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
                     bw if bw == Decimal::new(12_500, 0) => "0".to_string(), // 12.5kHz
                     _ => return Err("Unrecognized bandwidth".into()),
                 },
-                "0".to_string(), // Scan List
+                write_scan_list(channel, codeplug), // Scan List
                 write_squelch(&channel.fm.as_ref().unwrap().squelch), // Squelch
                 "0".to_string(), // RX Ref Frequency
                 "0".to_string(), // TX Ref Frequency
@@ -488,15 +557,15 @@ fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
                 "0".to_string(), // Decode 6
                 "0".to_string(), // Decode 7
                 "0".to_string(), // Decode 8
-            ])?;
+            ]
         } else if channel.mode == ChannelMode::DMR {
-            writer.write_record(&[
+            [
                 "2".to_string(), // Channel Mode
                 channel.name.clone(), // Channel Name
                 format!("{:.5}", channel.frequency_rx / Decimal::new(1_000_000, 0)), // RX Frequency(MHz)
                 format!("{:.5}", channel.frequency_tx / Decimal::new(1_000_000, 0)), // TX Frequency(MHz)
                 "0".to_string(), // Band Width
-                "0".to_string(), // Scan List
+                write_scan_list(channel, codeplug), // Scan List
                 "1".to_string(), // Squelch
                 "0".to_string(), // RX Ref Frequency
                 "0".to_string(), // TX Ref Frequency
@@ -508,15 +577,15 @@ fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
                 if channel.rx_only { "1" } else { "0" }.to_string(), // Rx Only
                 "0".to_string(), // Lone Worker
                 "0".to_string(), // VOX
-                "0".to_string(), // Allow Talkaround
+                if channel.dmr.as_ref().unwrap().talkaround == Some(true) { "1" } else { "0" }.to_string(), // Allow Talkaround
                 "0".to_string(), // Send GPS Info
                 "0".to_string(), // Receive GPS Info
                 "0".to_string(), // Private Call Confirmed
                 "0".to_string(), // Emergency Alarm Ack
                 "0".to_string(), // Data Call Confirmed
                 "0".to_string(), // Allow Interrupt
-                "0".to_string(), // DCDCM Switch
-                "1".to_string(), // Leader/MS
+                if channel.dmr.as_ref().unwrap().simplex_tdma == Some(true) { "1" } else { "0" }.to_string(), // DCDM Switch
+                if channel.dmr.as_ref().unwrap().dcdm_leader == Some(true) { "0" } else { "1" }.to_string(), // Leader/MS
                 "0".to_string(), // Emergency System
                 write_contact(&channel, &codeplug), // Contact Name
                 write_group_list(&channel, &codeplug), // Group List
@@ -542,11 +611,16 @@ fn write_channels(codeplug: &Codeplug, path: &PathBuf, opt: &Opt) -> Result<(),
                 "0".to_string(), // Decode 6
                 "0".to_string(), // Decode 7
                 "0".to_string(), // Decode 8
-            ])?;
-        }
+            ]
+        } else {
+            continue;
+        };
+        super::apply_extensions(&header, &mut record, &channel_mapped(channel), &channel.extensions, model(), &mut dropped);
+        writer.write_record(&record)?;
     }
 
     writer.flush()?;
+    super::warn_dropped_extensions(path, &dropped, opt);
     Ok(())
 }
 
@@ -586,5 +660,12 @@ pub fn write(codeplug: &Codeplug, output_path: &PathBuf, opt: &Opt) -> Result<()
     channels_path.push("channels.csv");
     write_channels(&codeplug, &channels_path, opt)?;
 
+    if !codeplug.broadcast_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Broadcast memories are not supported, skipping {} memories", codeplug.broadcast_memories.len());
+    }
+    if !codeplug.airband_memories.is_empty() {
+        uprintln!(opt, Stderr, Color::Yellow, None, "Airband memories are not supported, skipping {} memories", codeplug.airband_memories.len());
+    }
+
     Ok(())
 }
@@ -0,0 +1,196 @@
+// src/report.rs
+
+use std::collections::HashMap;
+use crate::*;
+use crate::structures::*;
+use crate::validate::{Complaint, Severity};
+
+// Codeplug reports (HTML and Markdown) for publishing alongside a codeplug:
+// - channel tables per zone (and channels that are not in any zone)
+// - talkgroup directory and talkgroup list membership
+// - validation results (radio-specific if the codeplug source is a supported radio)
+// The report is built as a list of tables, then rendered to either format.
+
+struct Table {
+    title: String,
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+const CHANNEL_HEADER: [&str; 9] = ["#", "Name", "Mode", "RX", "TX", "Tone/CC", "TS", "Talkgroup", "TG List"];
+
+fn tone_str(tone: &Option<Tone>) -> String {
+    match tone {
+        Some(Tone::Ctcss(freq)) => format!("{:.1}", freq),
+        Some(Tone::Dcs(code)) => code.clone(),
+        None => "".to_string(),
+    }
+}
+
+fn channel_row(position: usize, channel: &Channel, talkgroups: &HashMap<&String, &String>, talkgroup_lists: &HashMap<&String, &String>) -> Vec<String> {
+    let mut tone = String::new();
+    let mut timeslot = String::new();
+    let mut talkgroup = String::new();
+    let mut talkgroup_list = String::new();
+    if let Some(fm) = &channel.fm {
+        let (rx, tx) = (tone_str(&fm.tone_rx), tone_str(&fm.tone_tx));
+        tone = if rx == tx { tx } else { format!("{}/{}", tx, rx) };
+    }
+    if let Some(dmr) = &channel.dmr {
+        tone = if tone.is_empty() { format!("CC{}", dmr.color_code) } else { format!("{} CC{}", tone, dmr.color_code) };
+        timeslot = dmr.timeslot.to_string();
+        // references are uids, show the names
+        talkgroup = dmr.talkgroup.as_ref().map_or(String::new(), |t| talkgroups.get(t).map_or(t, |n| *n).clone());
+        talkgroup_list = dmr.talkgroup_list.as_ref().map_or(String::new(), |t| talkgroup_lists.get(t).map_or(t, |n| *n).clone());
+    }
+    vec![
+        position.to_string(),
+        channel.name.clone(),
+        format!("{:?}", channel.mode),
+        freq2str(&channel.frequency_rx).trim().to_string(),
+        if channel.rx_only { "RX only".to_string() } else { freq2str(&channel.frequency_tx).trim().to_string() },
+        tone,
+        timeslot,
+        talkgroup,
+        talkgroup_list,
+    ]
+}
+
+fn build_tables(opt: &Opt, codeplug: &Codeplug) -> Result<Vec<Table>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let mut tables = Vec::new();
+    let channels: HashMap<&String, &Channel> = codeplug.channels.iter().map(|c| (&c.uid, c)).collect();
+    let talkgroups: HashMap<&String, &String> = codeplug.talkgroups.iter().map(|t| (&t.uid, &t.name)).collect();
+    let talkgroup_lists: HashMap<&String, &String> = codeplug.talkgroup_lists.iter().map(|t| (&t.uid, &t.name)).collect();
+
+    // channels by zone, in the order they appear on the radio
+    for zone in &codeplug.zones {
+        let rows = zone.channels.iter().enumerate()
+            .filter_map(|(ii, uid)| channels.get(uid).map(|c| channel_row(ii + 1, c, &talkgroups, &talkgroup_lists)))
+            .collect();
+        tables.push(Table { title: format!("Zone: {}", zone.name), header: CHANNEL_HEADER.to_vec(), rows });
+    }
+    let unzoned: Vec<&Channel> = codeplug.channels.iter()
+        .filter(|c| !codeplug.zones.iter().any(|z| z.channels.contains(&c.uid)))
+        .collect();
+    if !unzoned.is_empty() || codeplug.zones.is_empty() {
+        let rows = unzoned.iter()
+            .map(|c| channel_row(c.index, c, &talkgroups, &talkgroup_lists))
+            .collect();
+        tables.push(Table { title: "Channels not in a zone".to_string(), header: CHANNEL_HEADER.to_vec(), rows });
+    }
+
+    // talkgroup directory
+    tables.push(Table {
+        title: "Talkgroups".to_string(),
+        header: vec!["Name", "ID", "Type", "TS"],
+        rows: codeplug.talkgroups.iter().map(|tg| vec![
+            tg.name.clone(),
+            tg.id.to_string(),
+            format!("{:?}", tg.call_type),
+            tg.timeslot_override.map_or(String::new(), |ts| ts.to_string()),
+        ]).collect(),
+    });
+
+    // talkgroup list membership
+    tables.push(Table {
+        title: "Talkgroup Lists".to_string(),
+        header: vec!["Name", "Talkgroups"],
+        rows: codeplug.talkgroup_lists.iter().map(|list| vec![
+            list.name.clone(),
+            list.talkgroups.iter().map(|tg| tg.name.clone()).collect::<Vec<String>>().join(", "),
+        ]).collect(),
+    });
+
+    // validation results, radio-specific checks only if the source is a radio we know
+    let properties = radios::find_properties(&codeplug.source);
+    let complaints: Vec<Complaint> = validate::check_codeplug(opt, codeplug, properties.as_ref())?;
+    tables.push(Table {
+        title: "Validation".to_string(),
+        header: vec!["Severity", "#", "Name", "Message"],
+        rows: complaints.iter().map(|c| vec![
+            match c.severity {
+                Severity::Error => "Error",
+                Severity::Warning => "Warning",
+                Severity::Info => "Info",
+            }.to_string(),
+            c.source_index.map_or(String::new(), |i| i.to_string()),
+            c.source_name.clone().unwrap_or_default(),
+            c.message.clone(),
+        ]).collect(),
+    });
+
+    Ok(tables)
+}
+
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Render a codeplug report as a self-contained HTML page
+pub fn html(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let tables = build_tables(opt, codeplug)?;
+    let title = format!("Codeplug ({})", codeplug.source);
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+    output.push_str("<style>\n");
+    output.push_str("body { font-family: sans-serif; }\n");
+    output.push_str("table { border-collapse: collapse; margin-bottom: 1.5em; }\n");
+    output.push_str("th, td { border: 1px solid #999; padding: 0.2em 0.5em; text-align: left; }\n");
+    output.push_str("th { background: #eee; }\n");
+    output.push_str("</style>\n</head>\n<body>\n");
+    output.push_str(&format!("<h1>{}</h1>\n", escape_html(&title)));
+    output.push_str(&format!("<p>{} channels, {} zones, {} talkgroups, {} talkgroup lists</p>\n",
+        codeplug.channels.len(), codeplug.zones.len(), codeplug.talkgroups.len(), codeplug.talkgroup_lists.len()));
+    for table in &tables {
+        output.push_str(&format!("<h2>{}</h2>\n", escape_html(&table.title)));
+        if table.rows.is_empty() {
+            output.push_str("<p>None</p>\n");
+            continue;
+        }
+        output.push_str("<table>\n<tr>");
+        for column in &table.header {
+            output.push_str(&format!("<th>{}</th>", escape_html(column)));
+        }
+        output.push_str("</tr>\n");
+        for row in &table.rows {
+            output.push_str("<tr>");
+            for cell in row {
+                output.push_str(&format!("<td>{}</td>", escape_html(cell)));
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n");
+    }
+    output.push_str("</body>\n</html>\n");
+    Ok(output)
+}
+
+/// Render a codeplug report as Markdown (GitHub-flavored tables)
+pub fn markdown(opt: &Opt, codeplug: &Codeplug) -> Result<String, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let tables = build_tables(opt, codeplug)?;
+    let mut output = String::new();
+    output.push_str(&format!("# Codeplug ({})\n\n", escape_markdown(&codeplug.source)));
+    output.push_str(&format!("{} channels, {} zones, {} talkgroups, {} talkgroup lists\n",
+        codeplug.channels.len(), codeplug.zones.len(), codeplug.talkgroups.len(), codeplug.talkgroup_lists.len()));
+    for table in &tables {
+        output.push_str(&format!("\n## {}\n\n", escape_markdown(&table.title)));
+        if table.rows.is_empty() {
+            output.push_str("None\n");
+            continue;
+        }
+        output.push_str(&format!("| {} |\n", table.header.join(" | ")));
+        output.push_str(&format!("|{}\n", "---|".repeat(table.header.len())));
+        for row in &table.rows {
+            output.push_str(&format!("| {} |\n", row.iter().map(|c| escape_markdown(c)).collect::<Vec<String>>().join(" | ")));
+        }
+    }
+    Ok(output)
+}
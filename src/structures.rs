@@ -1,9 +1,16 @@
 // src/structures.rs
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::BTreeMap;
+
+/// Vendor extensions (settings not modeled here), keyed by radio model and then by setting name
+// only used when writing to the same model they were read from, so editing a codeplug does not
+// reset settings plungle does not understand
+pub type Extensions = BTreeMap<String, BTreeMap<String, String>>;
 
 /// Channel mode
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub enum ChannelMode {
     #[default]
     AM,
@@ -12,7 +19,7 @@ pub enum ChannelMode {
 }
 
 /// Squelch
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub enum Squelch {
     #[default]
     Default,
@@ -20,14 +27,14 @@ pub enum Squelch {
 }
 
 /// Tone
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub enum Tone {
     Ctcss(f64),
     Dcs(String),
 }
 
 /// Channel FM properties
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct FmChannel {
     pub bandwidth: rust_decimal::Decimal,
     pub squelch: Squelch,
@@ -35,18 +42,35 @@ pub struct FmChannel {
     pub tone_tx: Option<Tone>,
 }
 
+/// DMR Talker Alias (what is transmitted as talker alias)
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
+pub enum TalkerAlias {
+    Off,
+    Aprs, // APRS position
+    Text, // callsign/name text
+    AprsText,
+}
+
 /// Channel DMR properties
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct DmrChannel {
     pub timeslot: u8,
     pub color_code: u8,
-    pub talkgroup: Option<String>,
-    pub talkgroup_list: Option<String>,
+    pub talkgroup: Option<String>, // talkgroup uid
+    pub talkgroup_list: Option<String>, // talkgroup list uid
     pub id_name: Option<String>,
+    pub talker_alias_ts1: Option<TalkerAlias>, // None is the radio's default
+    pub talker_alias_ts2: Option<TalkerAlias>, // None is the radio's default
+    // direct mode and talkaround options, None is the radio's default
+    pub simplex_tdma: Option<bool>, // TDMA direct mode (Anytone Simplex TDMA, TYT DCDM dual capacity direct mode)
+    pub tdma_adaptive: Option<bool>, // adaptive timeslot for TDMA direct mode (Anytone Slot Suit/TDMA Adaptive)
+    pub dcdm_leader: Option<bool>, // true if this radio is the DCDM timing leader, false for MS (TYT Leader/MS)
+    pub talkaround: Option<bool>, // transmit on the RX frequency
+    pub reverse: Option<bool>, // swap RX and TX frequencies
 }
 
 /// Timeout
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub enum Timeout {
     #[default]
     Default,
@@ -55,7 +79,7 @@ pub enum Timeout {
 }
 
 /// Power
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub enum Power {
     #[default]
     Default,
@@ -64,7 +88,7 @@ pub enum Power {
 
 /// Tx Permit
 // aka TX Admit, TX Authority, TX Inhibit
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub enum TxPermit {
     #[default]
     Always,
@@ -75,25 +99,27 @@ pub enum TxPermit {
 }
 
 /// ScanSkip
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct ScanSkip {
     pub zone: bool,
     pub all: bool,
 }
 
 /// Scan
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub enum Scan {
     Skip(ScanSkip),
-    ScanList(String),
+    ScanList(String), // scan list uid
 }
 
 /// Channel
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub struct Channel {
     pub index: usize,
+    #[serde(default)]
+    pub uid: String, // stable ID that other entities reference, names are display-only
     pub name: String,
-    pub mode: ChannelMode,
+    pub mode: ChannelMode, // TX mode when both fm and dmr are set (mixed mode)
     pub frequency_rx: rust_decimal::Decimal,
     pub frequency_tx: rust_decimal::Decimal,
     pub rx_only: bool,
@@ -104,18 +130,25 @@ pub struct Channel {
     // mode-specific properties
     pub fm: Option<FmChannel>,
     pub dmr: Option<DmrChannel>,
+    pub extensions: Option<Extensions>,
 }
 
 /// Zone (a zone is a collection of channels)
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct Zone {
     pub index: usize,
+    #[serde(default)]
+    pub uid: String, // stable ID that other entities reference, names are display-only
     pub name: String,
-    pub channels: Vec<String>,
+    pub channels: Vec<String>, // channel uids
+    pub a_channel: Option<String>, // channel selected on VFO A when entering the zone, must be a member
+    pub b_channel: Option<String>, // channel selected on VFO B when entering the zone, must be a member
+    pub hidden: Option<bool>,
+    pub extensions: Option<Extensions>,
 }
 
 /// DMR TalkgroupCallType
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub enum DmrTalkgroupCallType {
     Group,
     Private,
@@ -123,58 +156,149 @@ pub enum DmrTalkgroupCallType {
 }
 
 /// DMR Talkgroup
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct DmrTalkgroup {
     pub index: usize,
+    #[serde(default)]
+    pub uid: String, // stable ID that other entities reference, names are display-only
     pub id: u32,
     pub name: String,
     pub call_type: DmrTalkgroupCallType,
     pub alert: bool,
+    pub timeslot_override: Option<u8>, // transmit on this timeslot regardless of the channel, None to use the channel timeslot
+    pub extensions: Option<Extensions>,
 }
 
 /// DMR Talkgroup List
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct DmrTalkgroupList {
     pub index: usize,
+    #[serde(default)]
+    pub uid: String, // stable ID that other entities reference, names are display-only
     pub name: String,
     pub talkgroups: Vec<DmrTalkgroup>,
+    pub extensions: Option<Extensions>,
+}
+
+/// DMR Contact (user directory entry for private calls, kept separately from talkgroups)
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
+pub struct DmrContact {
+    pub index: usize,
+    pub id: u32,
+    pub callsign: String,
+    pub name: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>, // state or province
+    pub country: Option<String>,
+    pub remarks: Option<String>,
+    pub extensions: Option<Extensions>,
+}
+
+/// Scan List Channel (priority channel reference)
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
+pub enum ScanListChannel {
+    Selected, // the currently selected channel
+    Channel(String), // channel uid
+}
+
+/// Scan List Revert Channel (channel to transmit on when PTT is pressed while scanning)
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
+pub enum ScanRevert {
+    Selected,
+    SelectedTalkback, // selected, but talkback on the channel that stopped the scan
+    Priority1,
+    Priority2,
+    Priority1Talkback,
+    Priority2Talkback,
+    LastCalled,
+    LastUsed,
+    Channel(String), // channel uid
 }
 
 /// Scan List
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub struct ScanList {
     pub index: usize,
+    #[serde(default)]
+    pub uid: String, // stable ID that other entities reference, names are display-only
     pub name: String,
-    pub channels: Vec<String>,
+    pub channels: Vec<String>, // channel uids
+    // unset fields (None) are left at the radio's default when writing
+    pub scan_mode: Option<bool>,
+    pub priority_channel_1: Option<ScanListChannel>, // None if disabled
+    pub priority_channel_2: Option<ScanListChannel>, // None if disabled
+    pub revert_channel: Option<ScanRevert>,
+    pub look_back_time_a: Option<f64>, // seconds
+    pub look_back_time_b: Option<f64>, // seconds
+    pub dropout_delay_time: Option<f64>, // seconds
+    pub dwell_time: Option<f64>, // seconds
+    pub extensions: Option<Extensions>,
+}
+
+/// Receive Memory (broadcast FM or airband AM memories, kept separately from channels)
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
+pub struct ReceiveMemory {
+    pub index: usize,
+    pub name: String, // may be empty, not all radios name these
+    pub frequency: rust_decimal::Decimal,
+    pub scan: bool,
 }
 
 /// DMR ID
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct DmrId {
     pub id: u32,
     pub name: String,
 }
 
 /// DMR Configuration
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct DmrConfiguration {
     pub id_list: Vec<DmrId>,
 }
 
+/// General Settings (radio-wide settings, boot text, defaults)
+// unset fields (None or Default) are left at the radio's default when writing
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
+pub struct GeneralSettings {
+    pub intro_line_1: Option<String>,
+    pub intro_line_2: Option<String>,
+    pub beep: Option<bool>,
+    pub tx_tot: Timeout,
+    pub mic_level: Option<u8>, // 1-10
+    pub vox_level: Option<u8>, // 0-10, 0 is disabled
+    pub squelch: Squelch,
+    pub power_save: Option<bool>,
+    pub backlight_timeout: Timeout, // Infinite is always on
+    // raw settings not covered above, only meaningful to the radio in Codeplug.source
+    pub raw: Option<BTreeMap<String, String>>,
+}
+
 /// Configuration (radio options, settings, and user data/IDs/callsigns)
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 pub struct Configuration {
     pub dmr_configuration: Option<DmrConfiguration>,
+    pub general_settings: Option<GeneralSettings>,
+    // auto-repeater offset table (Hz), None is filled from the bandplan when writing
+    pub auto_repeater_offsets: Option<Vec<rust_decimal::Decimal>>,
 }
 
 /// Codeplug
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone, Default)]
 pub struct Codeplug {
+    #[serde(default)]
+    pub schema_version: u32, // intermediary format version, see migrate.rs
     pub channels: Vec<Channel>,
     pub zones: Vec<Zone>,
     pub scanlists: Vec<ScanList>,
     pub talkgroups: Vec<DmrTalkgroup>,
     pub talkgroup_lists: Vec<DmrTalkgroupList>,
+    #[serde(default)]
+    pub contacts: Vec<DmrContact>, // DMR user directory
+    #[serde(default)]
+    pub broadcast_memories: Vec<ReceiveMemory>, // broadcast FM (WFM)
+    #[serde(default)]
+    pub airband_memories: Vec<ReceiveMemory>, // airband AM
     pub config: Option<Configuration>,
     pub source: String, // source radio
 }
@@ -187,6 +311,13 @@ pub struct RadioProperties {
     pub channel_name_width_max: usize,
     pub zones_max: usize,
     pub zone_name_width_max: usize,
+    pub scanlist_name_width_max: usize, // 0 if the radio has no scan lists
+    pub talkgroup_name_width_max: usize, // 0 if the radio has no talkgroups
+    pub talkgroup_list_name_width_max: usize, // 0 if the radio has no talkgroup lists
+    pub contacts_max: usize, // 0 if the radio has no separate contact list
+    pub mixed_mode: bool, // channels can have both fm and dmr set
+    pub ctcss_custom: bool, // one RX CTCSS frequency outside 62.5-254.1 can be written (e.g. Anytone Custom CTCSS)
+    pub dcs_custom: bool, // all 512 DCS codes can be written, not just the standard 104
     // dynamically set
     pub channel_index_width: usize,
     pub zone_index_width: usize,
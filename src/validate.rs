@@ -1,12 +1,16 @@
 // src/validate.rs
 
+use std::collections::{HashMap, HashSet};
 use rust_decimal::prelude::*;
+use clap::ValueEnum;
+use serde::Serialize;
 use crate::*;
-use crate::structures::Codeplug;
+use crate::structures::{Codeplug, DmrTalkgroupCallType, Scan, ScanListChannel, ScanRevert, Tone};
 use crate::bandplan::Bandplan;
 
 /// Severity
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
@@ -15,7 +19,7 @@ pub enum Severity {
 }
 
 /// Complaint
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq, Serialize)]
 pub struct Complaint {
     pub severity: Severity,
     pub message: String,
@@ -132,6 +136,350 @@ pub fn validate_generic(opt: &Opt, codeplug: &structures::Codeplug, bandplan: &B
             }
         }
     }
+    for zone in &codeplug.zones {
+        // selected A/B channels must be members of the zone
+        for (vfo, selected) in [("A", &zone.a_channel), ("B", &zone.b_channel)] {
+            if let Some(uid) = selected {
+                // missing channels are reported by validate_references()
+                let Some(channel) = codeplug.channels.iter().find(|c| c.uid == *uid) else { continue };
+                if !zone.channels.contains(uid) {
+                    let name = &channel.name;
+                    complaints.push(Complaint {
+                        severity: Severity::Warning,
+                        message: format!("Zone {} channel is not a member of the zone: {}", vfo, name),
+                        source_index: Some(zone.index),
+                        source_name: Some(zone.name.clone()),
+                    });
+                }
+            }
+        }
+    }
+    Ok(complaints)
+}
+
+// a reference to an entity that doesn't exist
+fn missing(source_index: usize, source_name: &str, message: String) -> Complaint {
+    Complaint {
+        severity: Severity::Error,
+        message,
+        source_index: Some(source_index),
+        source_name: Some(source_name.to_string()),
+    }
+}
+
+// this function checks that every reference points at an entity that exists
+pub fn validate_references(opt: &Opt, codeplug: &Codeplug) -> Result<Vec<Complaint>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let mut complaints: Vec<Complaint> = Vec::new();
+    let channels: HashSet<&String> = codeplug.channels.iter().map(|c| &c.uid).collect();
+    let scanlists: HashSet<&String> = codeplug.scanlists.iter().map(|s| &s.uid).collect();
+    let talkgroups: HashSet<&String> = codeplug.talkgroups.iter().map(|t| &t.uid).collect();
+    let talkgroup_lists: HashSet<&String> = codeplug.talkgroup_lists.iter().map(|t| &t.uid).collect();
+    let radio_ids: HashSet<&String> = codeplug.config.iter()
+        .flat_map(|c| c.dmr_configuration.iter())
+        .flat_map(|d| d.id_list.iter().map(|id| &id.name))
+        .collect();
+    for channel in &codeplug.channels {
+        if let Some(Scan::ScanList(uid)) = &channel.scan {
+            if !scanlists.contains(uid) {
+                complaints.push(missing(channel.index, &channel.name, format!("Channel references missing scan list: {}", uid)));
+            }
+        }
+        if let Some(dmr) = &channel.dmr {
+            if let Some(uid) = &dmr.talkgroup {
+                if !talkgroups.contains(uid) {
+                    complaints.push(missing(channel.index, &channel.name, format!("Channel references missing talkgroup: {}", uid)));
+                }
+            }
+            if let Some(uid) = &dmr.talkgroup_list {
+                if !talkgroup_lists.contains(uid) {
+                    complaints.push(missing(channel.index, &channel.name, format!("Channel references missing talkgroup list: {}", uid)));
+                }
+            }
+            // radio IDs are referenced by name
+            if let Some(name) = &dmr.id_name {
+                if !radio_ids.contains(name) {
+                    complaints.push(missing(channel.index, &channel.name, format!("Channel references missing radio ID: {}", name)));
+                }
+            }
+        }
+    }
+    for zone in &codeplug.zones {
+        for uid in &zone.channels {
+            if !channels.contains(uid) {
+                complaints.push(missing(zone.index, &zone.name, format!("Zone references missing channel: {}", uid)));
+            }
+        }
+        for (vfo, selected) in [("A", &zone.a_channel), ("B", &zone.b_channel)] {
+            if let Some(uid) = selected {
+                if !channels.contains(uid) && !zone.channels.contains(uid) {
+                    complaints.push(missing(zone.index, &zone.name, format!("Zone {} channel references missing channel: {}", vfo, uid)));
+                }
+            }
+        }
+    }
+    for scanlist in &codeplug.scanlists {
+        for uid in &scanlist.channels {
+            if !channels.contains(uid) {
+                complaints.push(missing(scanlist.index, &scanlist.name, format!("Scan list references missing channel: {}", uid)));
+            }
+        }
+        let priority = [("priority channel 1", &scanlist.priority_channel_1), ("priority channel 2", &scanlist.priority_channel_2)];
+        for (field, selected) in priority {
+            if let Some(ScanListChannel::Channel(uid)) = selected {
+                if !channels.contains(uid) {
+                    complaints.push(missing(scanlist.index, &scanlist.name, format!("Scan list {} references missing channel: {}", field, uid)));
+                }
+            }
+        }
+        if let Some(ScanRevert::Channel(uid)) = &scanlist.revert_channel {
+            if !channels.contains(uid) {
+                complaints.push(missing(scanlist.index, &scanlist.name, format!("Scan list revert channel references missing channel: {}", uid)));
+            }
+        }
+    }
+    for talkgroup_list in &codeplug.talkgroup_lists {
+        // members are copies, they must still be in the talkgroups
+        for talkgroup in &talkgroup_list.talkgroups {
+            if !talkgroups.contains(&talkgroup.uid) {
+                complaints.push(missing(talkgroup_list.index, &talkgroup_list.name, format!("Talkgroup list references missing talkgroup: {}", talkgroup.name)));
+            }
+        }
+    }
+    Ok(complaints)
+}
+
+// compare names exactly, ignoring case, and after truncation to the radio's display width (0 to skip)
+// referenced entities are written to the radio by name, so exact duplicates are errors
+fn duplicate_names(kind: &str, entities: &[(usize, &String)], width: usize, referenced: bool) -> Vec<Complaint> {
+    let mut complaints: Vec<Complaint> = Vec::new();
+    let mut exact: HashMap<String, (usize, &String)> = HashMap::new();
+    let mut folded: HashMap<String, (usize, &String)> = HashMap::new();
+    let mut truncated: HashMap<String, (usize, &String)> = HashMap::new();
+    for (index, name) in entities {
+        let lower = name.to_lowercase();
+        let short: String = lower.chars().take(width).collect();
+        let complaint = if let Some((first_index, _)) = exact.get(name.as_str()) {
+            Some((if referenced { Severity::Error } else { Severity::Warning },
+                format!("Duplicate name (same as {} {})", kind, first_index)))
+        } else if let Some((first_index, first_name)) = folded.get(&lower) {
+            Some((Severity::Warning, format!("Name differs only in case from {} {} ({})", kind, first_index, first_name)))
+        } else if let Some((first_index, first_name)) = truncated.get(&short).filter(|_| width > 0) {
+            Some((Severity::Warning, format!("Name collides with {} {} ({}) when truncated to {} characters: {}",
+                kind, first_index, first_name, width, name.chars().take(width).collect::<String>())))
+        } else {
+            None
+        };
+        if let Some((severity, message)) = complaint {
+            complaints.push(Complaint {
+                severity,
+                message,
+                source_index: Some(*index),
+                source_name: Some(name.to_string()),
+            });
+        }
+        exact.entry(name.to_string()).or_insert((*index, name));
+        folded.entry(lower).or_insert((*index, name));
+        truncated.entry(short).or_insert((*index, name));
+    }
+    complaints
+}
+
+// this function checks for names that are the same, or would be the same on the radio
+pub fn validate_names(opt: &Opt, codeplug: &Codeplug, props: Option<&structures::RadioProperties>) -> Result<Vec<Complaint>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let mut complaints: Vec<Complaint> = Vec::new();
+    // without a target radio, only exact and case-insensitive duplicates are found
+    let width = |f: fn(&structures::RadioProperties) -> usize| props.map_or(0, f);
+    let channels: Vec<(usize, &String)> = codeplug.channels.iter().map(|c| (c.index, &c.name)).collect();
+    complaints.extend(duplicate_names("channel", &channels, width(|p| p.channel_name_width_max), true));
+    let zones: Vec<(usize, &String)> = codeplug.zones.iter().map(|z| (z.index, &z.name)).collect();
+    complaints.extend(duplicate_names("zone", &zones, width(|p| p.zone_name_width_max), false));
+    let scanlists: Vec<(usize, &String)> = codeplug.scanlists.iter().map(|s| (s.index, &s.name)).collect();
+    complaints.extend(duplicate_names("scan list", &scanlists, width(|p| p.scanlist_name_width_max), true));
+    let talkgroups: Vec<(usize, &String)> = codeplug.talkgroups.iter().map(|t| (t.index, &t.name)).collect();
+    complaints.extend(duplicate_names("talkgroup", &talkgroups, width(|p| p.talkgroup_name_width_max), true));
+    let talkgroup_lists: Vec<(usize, &String)> = codeplug.talkgroup_lists.iter().map(|t| (t.index, &t.name)).collect();
+    complaints.extend(duplicate_names("talkgroup list", &talkgroup_lists, width(|p| p.talkgroup_list_name_width_max), true));
+    Ok(complaints)
+}
+
+/// Standard CTCSS tones (Hz), the EIA tones plus the common extensions found on most radios
+pub const CTCSS_TONES: [f64; 50] = [
+    67.0, 69.3, 71.9, 74.4, 77.0, 79.7, 82.5, 85.4, 88.5, 91.5,
+    94.8, 97.4, 100.0, 103.5, 107.2, 110.9, 114.8, 118.8, 123.0, 127.3,
+    131.8, 136.5, 141.3, 146.2, 151.4, 156.7, 159.8, 162.2, 165.5, 167.9,
+    171.3, 173.8, 177.3, 179.9, 183.5, 186.2, 189.9, 192.8, 196.6, 199.5,
+    203.5, 206.5, 210.7, 218.1, 225.7, 229.1, 233.6, 241.8, 250.3, 254.1,
+];
+
+/// Standard DCS codes (octal, as written without the D prefix and N/I polarity)
+pub const DCS_CODES: [&str; 104] = [
+    "023", "025", "026", "031", "032", "036", "043", "047", "051", "053", "054", "065", "071",
+    "072", "073", "074", "114", "115", "116", "122", "125", "131", "132", "134", "143", "145",
+    "152", "155", "156", "162", "165", "172", "174", "205", "212", "223", "225", "226", "243",
+    "244", "245", "246", "251", "252", "255", "261", "263", "265", "266", "271", "274", "306",
+    "311", "315", "325", "331", "332", "343", "346", "351", "356", "364", "365", "371", "411",
+    "412", "413", "423", "431", "432", "445", "446", "452", "454", "455", "462", "464", "465",
+    "466", "503", "506", "516", "523", "526", "532", "546", "565", "606", "612", "624", "627",
+    "631", "632", "654", "662", "664", "703", "712", "723", "731", "732", "734", "743", "754",
+];
+
+// custom is the custom CTCSS tone already used by an earlier channel, radios with ctcss_custom have only one
+fn tone_complaint(direction: &str, tone: &Tone, props: Option<&structures::RadioProperties>, custom: &mut Option<f64>) -> Option<(Severity, String)> {
+    match tone {
+        Tone::Ctcss(freq) => {
+            let nearest = CTCSS_TONES.iter().copied()
+                .min_by(|a, b| (a - freq).abs().total_cmp(&(b - freq).abs()))
+                .unwrap();
+            // the custom tone is only used for RX tones outside the radio's tone list (see anytone_x78.rs)
+            let writes_custom = props.is_some_and(|p| p.ctcss_custom) && direction == "RX" && (*freq < 62.5 || *freq > 254.1);
+            if (nearest - freq).abs() < 0.05 {
+                None
+            } else if (nearest - freq).abs() <= 0.5 {
+                // close to a standard tone, most likely a typo
+                Some((Severity::Error, format!("Non-standard {} CTCSS tone: {:.1} (nearest standard tone: {:.1})", direction, freq, nearest)))
+            } else if let Some(used) = custom.filter(|c| writes_custom && (c - freq).abs() >= 0.05) {
+                Some((Severity::Error, format!("Non-standard {} CTCSS tone, only one custom tone is supported ({:.1} is already used): {:.1}",
+                    direction, used, freq)))
+            } else if writes_custom {
+                *custom = Some(*freq);
+                Some((Severity::Info, format!("Non-standard {} CTCSS tone, will be written as a custom tone: {:.1}", direction, freq)))
+            } else {
+                // radio tone lists vary beyond the standard tones (e.g. 62.5), so this may still be supported
+                Some((Severity::Warning, format!("Non-standard {} CTCSS tone, may not be supported: {:.1}", direction, freq)))
+            }
+        }
+        Tone::Dcs(code) => {
+            // D, three octal digits, then N (normal) or I (inverted), checked as ASCII first so slicing is safe
+            let valid = code.is_ascii() && code.len() == 5 && code.starts_with('D')
+                && code[1..4].chars().all(|c| ('0'..='7').contains(&c))
+                && (code.ends_with('N') || code.ends_with('I'));
+            if !valid {
+                Some((Severity::Error, format!("Invalid {} DCS code (expected e.g. D023N or D023I): {}", direction, code)))
+            } else if DCS_CODES.contains(&&code[1..4]) || props.is_some_and(|p| p.dcs_custom) {
+                None
+            } else if props.is_some() {
+                Some((Severity::Error, format!("Non-standard {} DCS code: {}", direction, code)))
+            } else {
+                Some((Severity::Warning, format!("Non-standard {} DCS code: {}", direction, code)))
+            }
+        }
+    }
+}
+
+// this function checks CTCSS tones and DCS codes against the standard tables,
+// and against the target radio's custom tone support if properties are given
+pub fn validate_tones(opt: &Opt, codeplug: &Codeplug, props: Option<&structures::RadioProperties>) -> Result<Vec<Complaint>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let mut complaints: Vec<Complaint> = Vec::new();
+    let mut custom: Option<f64> = None;
+    for channel in &codeplug.channels {
+        let Some(fm) = &channel.fm else { continue };
+        for (direction, tone) in [("RX", &fm.tone_rx), ("TX", &fm.tone_tx)] {
+            if let Some((severity, message)) = tone.as_ref().and_then(|t| tone_complaint(direction, t, props, &mut custom)) {
+                complaints.push(Complaint {
+                    severity,
+                    message,
+                    source_index: Some(channel.index),
+                    source_name: Some(channel.name.clone()),
+                });
+            }
+        }
+    }
+    Ok(complaints)
+}
+
+/// Largest DMR ID (24 bits), also the all call ID
+pub const DMR_ID_MAX: u32 = 16_777_215;
+
+// this function checks DMR fields against the ranges allowed by the DMR standard
+pub fn validate_dmr(opt: &Opt, codeplug: &Codeplug) -> Result<Vec<Complaint>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    let mut complaints: Vec<Complaint> = Vec::new();
+    for channel in &codeplug.channels {
+        let Some(dmr) = &channel.dmr else { continue };
+        if dmr.color_code > 15 {
+            complaints.push(Complaint {
+                severity: Severity::Error,
+                message: format!("Color code out of range (0-15): {}", dmr.color_code),
+                source_index: Some(channel.index),
+                source_name: Some(channel.name.clone()),
+            });
+        }
+        if dmr.timeslot != 1 && dmr.timeslot != 2 {
+            complaints.push(Complaint {
+                severity: Severity::Error,
+                message: format!("Timeslot must be 1 or 2: {}", dmr.timeslot),
+                source_index: Some(channel.index),
+                source_name: Some(channel.name.clone()),
+            });
+        }
+        // without either, the channel can receive but not transmit to anyone
+        if dmr.talkgroup.is_none() && dmr.talkgroup_list.is_none() {
+            complaints.push(Complaint {
+                severity: Severity::Warning,
+                message: "DMR channel has no talkgroup or talkgroup list".to_string(),
+                source_index: Some(channel.index),
+                source_name: Some(channel.name.clone()),
+            });
+        }
+    }
+    for talkgroup in &codeplug.talkgroups {
+        let mut complain = |severity: Severity, message: String| complaints.push(Complaint {
+            severity,
+            message,
+            source_index: Some(talkgroup.index),
+            source_name: Some(talkgroup.name.clone()),
+        });
+        if talkgroup.id == 0 || talkgroup.id > DMR_ID_MAX {
+            complain(Severity::Error, format!("Talkgroup ID out of range (1-{}): {}", DMR_ID_MAX, talkgroup.id));
+        }
+        if let Some(timeslot) = talkgroup.timeslot_override {
+            if timeslot != 1 && timeslot != 2 {
+                complain(Severity::Error, format!("Timeslot override must be 1 or 2: {}", timeslot));
+            }
+        }
+        match talkgroup.call_type {
+            DmrTalkgroupCallType::AllCall if talkgroup.id != DMR_ID_MAX => {
+                complain(Severity::Error, format!("All call ID must be {}: {}", DMR_ID_MAX, talkgroup.id));
+            }
+            DmrTalkgroupCallType::Group | DmrTalkgroupCallType::Private if talkgroup.id == DMR_ID_MAX => {
+                complain(Severity::Warning, format!("ID {} is the all call ID, but call type is {:?}", DMR_ID_MAX, talkgroup.call_type));
+            }
+            // radio IDs are 7 digits, network services (e.g. Brandmeister Parrot, 9990) are not
+            DmrTalkgroupCallType::Private if (1..=DMR_ID_MAX).contains(&talkgroup.id) && !(1_000_000..=9_999_999).contains(&talkgroup.id) => {
+                complain(Severity::Info, format!("Private call ID is not a 7-digit radio ID (may be a network service): {}", talkgroup.id));
+            }
+            _ => {}
+        }
+    }
+    // the contacts are a user directory, so every entry should be a radio ID
+    for contact in &codeplug.contacts {
+        let mut complain = |severity: Severity, message: String| complaints.push(Complaint {
+            severity,
+            message,
+            source_index: Some(contact.index),
+            source_name: Some(contact.callsign.clone()),
+        });
+        if contact.id == 0 || contact.id > DMR_ID_MAX {
+            complain(Severity::Error, format!("Contact ID out of range (1-{}): {}", DMR_ID_MAX, contact.id));
+        } else if !(1_000_000..=9_999_999).contains(&contact.id) {
+            complain(Severity::Warning, format!("Contact ID is not a 7-digit radio ID: {}", contact.id));
+        }
+    }
+    if let Some(dmr_configuration) = codeplug.config.as_ref().and_then(|c| c.dmr_configuration.as_ref()) {
+        for radio_id in &dmr_configuration.id_list {
+            if radio_id.id == 0 || radio_id.id > DMR_ID_MAX {
+                complaints.push(Complaint {
+                    severity: Severity::Error,
+                    message: format!("Radio ID {} out of range (1-{}): {}", radio_id.name, DMR_ID_MAX, radio_id.id),
+                    source_index: None,
+                    source_name: Some(radio_id.name.clone()),
+                });
+            }
+        }
+    }
     Ok(complaints)
 }
 
@@ -147,7 +495,14 @@ pub fn validate_specific(opt: &Opt, codeplug: &structures::Codeplug, props: &str
             source_name: None,
         });
     }
-    if codeplug.zones.len() > props.zones_max as usize {
+    if props.zones_max == 0 && !codeplug.zones.is_empty() {
+        complaints.push(Complaint {
+            severity: Severity::Warning,
+            message: format!("Zones not supported, {} zones will be dropped", codeplug.zones.len()),
+            source_index: None,
+            source_name: None,
+        });
+    } else if codeplug.zones.len() > props.zones_max as usize {
         complaints.push(Complaint {
             severity: Severity::Error,
             message: format!("Too many zones: {} (max: {})", codeplug.zones.len(), props.zones_max),
@@ -155,6 +510,21 @@ pub fn validate_specific(opt: &Opt, codeplug: &structures::Codeplug, props: &str
             source_name: None,
         });
     }
+    if props.contacts_max == 0 && !codeplug.contacts.is_empty() {
+        complaints.push(Complaint {
+            severity: Severity::Warning,
+            message: format!("Contacts not supported, {} contacts will be dropped", codeplug.contacts.len()),
+            source_index: None,
+            source_name: None,
+        });
+    } else if codeplug.contacts.len() > props.contacts_max {
+        complaints.push(Complaint {
+            severity: Severity::Error,
+            message: format!("Too many contacts: {} (max: {})", codeplug.contacts.len(), props.contacts_max),
+            source_index: None,
+            source_name: None,
+        });
+    }
     // check channels
     for channel in &codeplug.channels {
         if channel.name.len() > props.channel_name_width_max  {
@@ -173,6 +543,15 @@ pub fn validate_specific(opt: &Opt, codeplug: &structures::Codeplug, props: &str
                 source_name: Some(channel.name.clone()),
             });
         }
+        // writers for radios without mixed mode only write the TX mode
+        if !props.mixed_mode && channel.fm.is_some() && channel.dmr.is_some() {
+            complaints.push(Complaint {
+                severity: Severity::Warning,
+                message: format!("Mixed mode not supported, channel will be {:?} only", channel.mode),
+                source_index: Some(channel.index),
+                source_name: Some(channel.name.clone()),
+            });
+        }
     }
     // check zones
     for zone in &codeplug.zones {
@@ -188,22 +567,151 @@ pub fn validate_specific(opt: &Opt, codeplug: &structures::Codeplug, props: &str
     Ok(complaints)
 }
 
-pub fn validate_codeplug(opt: &Opt, codeplug: &Codeplug, model: &String) -> Result<(), Box<dyn Error>> {
+// generic validation, plus radio-specific validation if properties are given
+pub fn check_codeplug(opt: &Opt, codeplug: &Codeplug, properties: Option<&structures::RadioProperties>) -> Result<Vec<Complaint>, Box<dyn Error>> {
     uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
     let mut complaints: Vec<Complaint> = Vec::new();
     // load a band plan
     let bandplan = bandplan::load_bandplan(opt)?;
     // generic validation
-    complaints.extend(validate_generic(opt, codeplug, &bandplan).unwrap());
-    // radio-specific validation
-    let properties = radios::get_properties(opt, model).unwrap();
+    complaints.extend(validate_generic(opt, codeplug, &bandplan)?);
+    complaints.extend(validate_references(opt, codeplug)?);
+    complaints.extend(validate_names(opt, codeplug, properties)?);
+    complaints.extend(validate_tones(opt, codeplug, properties)?);
+    complaints.extend(validate_dmr(opt, codeplug)?);
     // specific validation
-    complaints.extend(validate_specific(opt, codeplug, &properties).unwrap());
+    if let Some(properties) = properties {
+        complaints.extend(validate_specific(opt, codeplug, properties)?);
+    }
+    Ok(complaints)
+}
+
+pub fn validate_codeplug(opt: &Opt, codeplug: &Codeplug, model: &String) -> Result<Vec<Complaint>, Box<dyn Error>> {
+    uprintln!(opt, Stderr, None, 2, "{}:{}()", file!(), function!());
+    // radio-specific validation
+    let properties = radios::get_properties(opt, model)?;
+    let complaints = check_codeplug(opt, codeplug, Some(&properties))?;
     // combine the complaints
     if !opt.quiet { // suppress output if --quiet
         print_complaints(opt, &complaints);
     }
-    Ok(())
+    Ok(complaints)
+}
+
+/// Lowest severity that makes `validate` exit non-zero
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Default)]
+pub enum FailOn {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Never,
+}
+
+/// Validation output format
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+}
+
+// true if any complaint is at or above the threshold
+pub fn fails(complaints: &[Complaint], fail_on: FailOn) -> bool {
+    complaints.iter().any(|c| match fail_on {
+        FailOn::Error => c.severity == Severity::Error,
+        FailOn::Warning => c.severity != Severity::Info,
+        FailOn::Info => true,
+        FailOn::Never => false,
+    })
+}
+
+fn count(complaints: &[Complaint], severity: Severity) -> usize {
+    complaints.iter().filter(|c| c.severity == severity).count()
+}
+
+/// Complaints as JSON, with totals
+pub fn complaints_json(complaints: &[Complaint], input: &std::path::Path, model: &str) -> Result<String, Box<dyn Error>> {
+    let json = serde_json::json!({
+        "input": input,
+        "model": model,
+        "errors": count(complaints, Severity::Error),
+        "warnings": count(complaints, Severity::Warning),
+        "infos": count(complaints, Severity::Info),
+        "complaints": complaints,
+    });
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+/// Complaints as a SARIF 2.1.0 log (for code scanning in CI), the codeplug file is the artifact and
+/// the channel/zone is the logical location
+pub fn complaints_sarif(complaints: &[Complaint], input: &std::path::Path, model: &str) -> Result<String, Box<dyn Error>> {
+    let uri = input.to_string_lossy().replace('\\', "/");
+    let results: Vec<serde_json::Value> = complaints.iter().map(|c| {
+        let mut location = serde_json::json!({
+            "physicalLocation": { "artifactLocation": { "uri": uri } },
+        });
+        if let Some(name) = &c.source_name {
+            location["logicalLocations"] = serde_json::json!([{ "name": name }]);
+        }
+        let mut result = serde_json::json!({
+            "level": match c.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "note",
+            },
+            "message": { "text": c.message },
+            "locations": [location],
+        });
+        if let Some(index) = c.source_index {
+            result["properties"] = serde_json::json!({ "index": index });
+        }
+        result
+    }).collect();
+    let sarif = serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": {
+                "name": "plungle",
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": "https://github.com/ayoungblood/plungle",
+            } },
+            "properties": { "model": model },
+            "results": results,
+        }],
+    });
+    Ok(serde_json::to_string_pretty(&sarif)?)
+}
+
+fn complaint_line(complaint: &Complaint) -> String {
+    let line = match (complaint.source_index, &complaint.source_name) {
+        (Some(index), Some(name)) => format!("{:4} {:24} {}", index, name, complaint.message),
+        _ => complaint.message.clone(),
+    };
+    match complaint.severity {
+        Severity::Error => format!("[Error  ] {}", line),
+        Severity::Warning => format!("[Warning] {}", line),
+        Severity::Info => format!("[Info   ] {}", line),
+    }
+}
+
+fn summary_line(complaints: &[Complaint]) -> String {
+    format!("Validation: {} errors, {} warnings, {} infos",
+        count(complaints, Severity::Error), count(complaints, Severity::Warning), count(complaints, Severity::Info))
+}
+
+/// Complaints as plain text, as printed by print_complaints()
+pub fn complaints_text(complaints: &[Complaint]) -> String {
+    let mut output = String::new();
+    for complaint in complaints {
+        output.push_str(&complaint_line(complaint));
+        output.push('\n');
+    }
+    output.push_str(&summary_line(complaints));
+    output.push('\n');
+    output
 }
 
 pub fn print_complaints(opt: &Opt, complaints: &Vec<Complaint>) {
@@ -212,35 +720,221 @@ pub fn print_complaints(opt: &Opt, complaints: &Vec<Complaint>) {
 
     // print the complaints
     for complaint in complaints {
-        let line;
-        if complaint.source_index.is_some() && complaint.source_name.is_some() {
-            line = format!("{:4} {:24} {}", complaint.source_index.unwrap(), complaint.source_name.as_ref().unwrap(), complaint.message);
-        } else {
-            line = format!("{}", complaint.message);
-        }
+        let line = complaint_line(complaint);
         match complaint.severity {
             Severity::Error => {
-                uprintln!(opt, Stderr, Color::Red, None, "[Error  ] {}", line);
+                uprintln!(opt, Stderr, Color::Red, None, "{}", line);
             },
             Severity::Warning => {
-                uprintln!(opt, Stderr, Color::Yellow, None, "[Warning] {}", line);
+                uprintln!(opt, Stderr, Color::Yellow, None, "{}", line);
             },
             Severity::Info => {
-                uprintln!(opt, Stderr, Color::Cyan, None, "[Info   ] {}", line);
+                uprintln!(opt, Stderr, Color::Cyan, None, "{}", line);
             },
         }
     }
     // total everything up
-    let error_count = complaints.iter().filter(|c| c.severity == Severity::Error).count();
-    let warning_count = complaints.iter().filter(|c| c.severity == Severity::Warning).count();
-    let info_count = complaints.iter().filter(|c| c.severity == Severity::Info).count();
+    let error_count = count(complaints, Severity::Error);
+    let warning_count = count(complaints, Severity::Warning);
     uprintln!(opt, Stderr, Color::Magenta, None, "{}", "- ".repeat(40));
     if error_count > 0 {
-        uprintln!(opt, Stderr, Color::Red, None, "Validation: {} errors, {} warnings, {} infos", error_count, warning_count, info_count);
+        uprintln!(opt, Stderr, Color::Red, None, "{}", summary_line(complaints));
     } else if warning_count > 0 {
-        uprintln!(opt, Stderr, Color::Yellow, None, "Validation: {} errors, {} warnings, {} infos", error_count, warning_count, info_count);
+        uprintln!(opt, Stderr, Color::Yellow, None, "{}", summary_line(complaints));
     } else {
-        uprintln!(opt, Stderr, Color::Cyan, None, "Validation: {} errors, {} warnings, {} infos", error_count, warning_count, info_count);
+        uprintln!(opt, Stderr, Color::Cyan, None, "{}", summary_line(complaints));
     }
     uprintln!(opt, Stderr, Color::Magenta, None, "{}", "-".repeat(79));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use crate::structures::*;
+
+    fn opt() -> Opt {
+        Opt::parse_from(["plungle", "-q"])
+    }
+
+    fn channel(index: usize, name: &str) -> Channel {
+        Channel { index, uid: format!("ch{}", index), name: name.to_string(), ..Default::default() }
+    }
+
+    fn messages(complaints: &[Complaint]) -> Vec<(Severity, &str)> {
+        complaints.iter().map(|c| (c.severity, c.message.as_str())).collect()
+    }
+
+    #[test]
+    fn missing_references_are_errors() {
+        let mut codeplug = Codeplug {
+            channels: vec![channel(1, "Simplex")],
+            zones: vec![Zone {
+                index: 1,
+                uid: "zone1".to_string(),
+                name: "VHF".to_string(),
+                channels: vec!["ch1".to_string(), "ch2".to_string()],
+                a_channel: Some("ch1".to_string()),
+                b_channel: Some("ch3".to_string()),
+                hidden: None,
+                extensions: None,
+            }],
+            scanlists: vec![ScanList {
+                index: 1,
+                uid: "sl1".to_string(),
+                name: "VHF".to_string(),
+                channels: vec!["ch1".to_string()],
+                scan_mode: None,
+                priority_channel_1: Some(ScanListChannel::Channel("ch4".to_string())),
+                priority_channel_2: Some(ScanListChannel::Selected),
+                revert_channel: Some(ScanRevert::Channel("ch1".to_string())),
+                look_back_time_a: None,
+                look_back_time_b: None,
+                dropout_delay_time: None,
+                dwell_time: None,
+                extensions: None,
+            }],
+            ..Default::default()
+        };
+        codeplug.channels[0].scan = Some(Scan::ScanList("sl2".to_string()));
+        let complaints = validate_references(&opt(), &codeplug).unwrap();
+        assert_eq!(messages(&complaints), vec![
+            (Severity::Error, "Channel references missing scan list: sl2"),
+            (Severity::Error, "Zone references missing channel: ch2"),
+            (Severity::Error, "Zone B channel references missing channel: ch3"),
+            (Severity::Error, "Scan list priority channel 1 references missing channel: ch4"),
+        ]);
+    }
+
+    #[test]
+    fn valid_references_are_clean() {
+        let mut codeplug = Codeplug { channels: vec![channel(1, "Simplex"), channel(2, "Calling")], ..Default::default() };
+        codeplug.channels[1].scan = Some(Scan::Skip(ScanSkip { zone: false, all: true }));
+        assert_eq!(messages(&validate_references(&opt(), &codeplug).unwrap()), vec![]);
+    }
+
+    #[test]
+    fn duplicate_names_by_severity() {
+        let names = ["Simplex", "Simplex", "SIMPLEX", "Simplex Calling", "Simplex Call"];
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let entities: Vec<(usize, &String)> = names.iter().enumerate().map(|(ii, n)| (ii + 1, n)).collect();
+        let complaints = duplicate_names("channel", &entities, 10, true);
+        assert_eq!(messages(&complaints), vec![
+            (Severity::Error, "Duplicate name (same as channel 1)"),
+            (Severity::Warning, "Name differs only in case from channel 1 (Simplex)"),
+            (Severity::Warning, "Name collides with channel 4 (Simplex Calling) when truncated to 10 characters: Simplex Ca"),
+        ]);
+        // unreferenced entities (zones) can share a name
+        let complaints = duplicate_names("zone", &entities[..2], 10, false);
+        assert_eq!(messages(&complaints), vec![(Severity::Warning, "Duplicate name (same as zone 1)")]);
+    }
+
+    #[test]
+    fn duplicate_names_without_width() {
+        // without a target radio, long names aren't truncated
+        let names = ["Simplex Calling", "Simplex Call"];
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let entities: Vec<(usize, &String)> = names.iter().enumerate().map(|(ii, n)| (ii + 1, n)).collect();
+        assert_eq!(messages(&duplicate_names("scan list", &entities, 0, true)), vec![]);
+    }
+
+    #[test]
+    fn duplicate_scan_list_names() {
+        let props = RadioProperties { scanlist_name_width_max: 4, ..Default::default() };
+        let scanlist = |index: usize, name: &str| ScanList {
+            index,
+            uid: format!("sl{}", index),
+            name: name.to_string(),
+            channels: vec![],
+            scan_mode: None,
+            priority_channel_1: None,
+            priority_channel_2: None,
+            revert_channel: None,
+            look_back_time_a: None,
+            look_back_time_b: None,
+            dropout_delay_time: None,
+            dwell_time: None,
+            extensions: None,
+        };
+        let codeplug = Codeplug { scanlists: vec![scanlist(1, "VHF1"), scanlist(2, "VHF1"), scanlist(3, "VHF10")], ..Default::default() };
+        let complaints = validate_names(&opt(), &codeplug, Some(&props)).unwrap();
+        assert_eq!(messages(&complaints), vec![
+            (Severity::Error, "Duplicate name (same as scan list 1)"),
+            (Severity::Warning, "Name collides with scan list 1 (VHF1) when truncated to 4 characters: VHF1"),
+        ]);
+    }
+
+    #[test]
+    fn standard_and_near_miss_tones() {
+        let mut custom = None;
+        assert_eq!(tone_complaint("RX", &Tone::Ctcss(100.0), None, &mut custom), None);
+        assert_eq!(tone_complaint("RX", &Tone::Dcs("D023N".to_string()), None, &mut custom), None);
+        let (severity, message) = tone_complaint("TX", &Tone::Ctcss(100.3), None, &mut custom).unwrap();
+        assert_eq!((severity, message.as_str()), (Severity::Error, "Non-standard TX CTCSS tone: 100.3 (nearest standard tone: 100.0)"));
+        let (severity, _) = tone_complaint("RX", &Tone::Dcs("D024N".to_string()), None, &mut custom).unwrap();
+        assert_eq!(severity, Severity::Warning);
+        let (severity, _) = tone_complaint("RX", &Tone::Dcs("D08N".to_string()), None, &mut custom).unwrap();
+        assert_eq!(severity, Severity::Error);
+        let (severity, _) = tone_complaint("RX", &Tone::Dcs("D12é".to_string()), None, &mut custom).unwrap();
+        assert_eq!(severity, Severity::Error);
+    }
+
+    #[test]
+    fn custom_ctcss_tone() {
+        let props = RadioProperties { ctcss_custom: true, ..Default::default() };
+        let severity = |direction: &str, freq: f64, custom: &mut Option<f64>| {
+            tone_complaint(direction, &Tone::Ctcss(freq), Some(&props), custom).map(|(severity, _)| severity)
+        };
+        let mut custom = None;
+        // only RX tones outside 62.5-254.1 are written as the custom tone
+        assert_eq!(severity("RX", 150.0, &mut custom), Some(Severity::Warning));
+        assert_eq!(severity("TX", 260.0, &mut custom), Some(Severity::Warning));
+        assert_eq!(custom, None);
+        assert_eq!(severity("RX", 260.0, &mut custom), Some(Severity::Info));
+        assert_eq!(severity("RX", 260.0, &mut custom), Some(Severity::Info));
+        assert_eq!(custom, Some(260.0));
+        // the radio has only one custom tone
+        assert_eq!(severity("RX", 55.0, &mut custom), Some(Severity::Error));
+        // without custom tone support, any non-standard tone may not be supported
+        let mut custom = None;
+        assert_eq!(tone_complaint("RX", &Tone::Ctcss(260.0), Some(&RadioProperties::default()), &mut custom).map(|(s, _)| s), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn dmr_id_ranges() {
+        let talkgroup = |index: usize, id: u32, call_type: DmrTalkgroupCallType| DmrTalkgroup {
+            index,
+            uid: format!("tg{}", index),
+            id,
+            name: format!("TG {}", id),
+            call_type,
+            alert: false,
+            timeslot_override: None,
+            extensions: None,
+        };
+        let contact = |index: usize, id: u32| DmrContact { index, id, callsign: format!("CALL{}", index), ..Default::default() };
+        let codeplug = Codeplug {
+            talkgroups: vec![
+                talkgroup(1, 91, DmrTalkgroupCallType::Group),
+                talkgroup(2, 0, DmrTalkgroupCallType::Group),
+                talkgroup(3, DMR_ID_MAX + 1, DmrTalkgroupCallType::Private),
+                talkgroup(4, 3_100_001, DmrTalkgroupCallType::Private),
+                talkgroup(5, 9990, DmrTalkgroupCallType::Private),
+                talkgroup(6, DMR_ID_MAX, DmrTalkgroupCallType::AllCall),
+            ],
+            contacts: vec![contact(1, 3_100_001), contact(2, 0), contact(3, DMR_ID_MAX + 1), contact(4, 12345)],
+            ..Default::default()
+        };
+        let complaints = validate_dmr(&opt(), &codeplug).unwrap();
+        let sources: Vec<(&str, Severity, &str)> = complaints.iter()
+            .map(|c| (c.source_name.as_deref().unwrap(), c.severity, c.message.as_str())).collect();
+        assert_eq!(sources, vec![
+            ("TG 0", Severity::Error, "Talkgroup ID out of range (1-16777215): 0"),
+            ("TG 16777216", Severity::Error, "Talkgroup ID out of range (1-16777215): 16777216"),
+            ("TG 9990", Severity::Info, "Private call ID is not a 7-digit radio ID (may be a network service): 9990"),
+            ("CALL2", Severity::Error, "Contact ID out of range (1-16777215): 0"),
+            ("CALL3", Severity::Error, "Contact ID out of range (1-16777215): 16777216"),
+            ("CALL4", Severity::Warning, "Contact ID is not a 7-digit radio ID: 12345"),
+        ]);
+    }
+}